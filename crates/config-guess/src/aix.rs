@@ -0,0 +1,61 @@
+use crate::{SystemProbe, Uname, command_stdout};
+
+pub(crate) fn ia64(probe: &dyn SystemProbe, uname: &Uname) -> String {
+    let rev = if probe.file_exists("/usr/bin/oslevel") {
+        command_stdout(probe, "/usr/bin/oslevel", &[]).unwrap_or_default()
+    } else {
+        format!("{}.{}", uname.version, uname.release)
+    };
+    format!("{}-ibm-aix{rev}", uname.machine)
+}
+
+pub(crate) fn aix3(probe: &dyn SystemProbe) -> String {
+    let stdio = probe.read_file("/usr/include/stdio.h").unwrap_or_default();
+    let stdio = String::from_utf8_lossy(&stdio);
+    if stdio.contains("bos325") {
+        let source = "#include <sys/systemcfg.h>
+
+int
+main ()
+	{
+	if (!__power_pc())
+		exit(1);
+	puts(\"powerpc-ibm-aix3.2.5\");
+	exit(0);
+	}
+";
+        match probe.compile_and_run(source) {
+            Some(output) if output.success => output.stdout.trim_end_matches('\n').into(),
+            _ => "rs6000-ibm-aix3.2.5".into(),
+        }
+    } else if stdio.contains("bos324") {
+        "rs6000-ibm-aix3.2.4".into()
+    } else {
+        "rs6000-ibm-aix3.2".into()
+    }
+}
+
+pub(crate) fn aix4(probe: &dyn SystemProbe, uname: &Uname) -> String {
+    let cpu_id = command_stdout(probe, "/usr/sbin/lsdev", &["-C", "-c", "processor", "-S", "available"])
+        .and_then(|devices| devices.lines().next()?.split_whitespace().next().map(String::from))
+        .unwrap_or_default();
+    let arch = match probe.command_output("/usr/sbin/lsattr", &["-El", &cpu_id]) {
+        Some(output) if output.stdout.contains(" POWER") => "rs6000",
+        _ => "powerpc",
+    };
+    let rev = if probe.file_exists("/usr/bin/lslpp") {
+        let lslpp = probe.command_output("/usr/bin/lslpp", &["-Lqc", "bos.rte.libc"]).unwrap_or_default();
+        lslpp
+            .stdout
+            .lines()
+            .map(|line| {
+                let level = line.split(':').nth(2).unwrap_or_default();
+                format!("{}0", level.trim_end_matches(|c: char| c.is_ascii_digit()))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        format!("{}.{}", uname.version, uname.release)
+    };
+    format!("{arch}-ibm-aix{rev}")
+}
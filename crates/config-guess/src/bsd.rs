@@ -0,0 +1,136 @@
+use crate::{SystemProbe, Uname, command_stdout, release_number};
+
+pub(crate) fn netbsd(probe: &dyn SystemProbe, uname: &Uname) -> String {
+    // NetBSD (nbsd) targets should (where applicable) match one or
+    // more of the tuples: *-*-netbsdelf*, *-*-netbsdaout*,
+    // *-*-netbsdecoff* and *-*-netbsd*.  For targets that recently
+    // switched to ELF, *-*-netbsd* would select the old
+    // object file format.  This provides both forward
+    // compatibility and a consistent mechanism for selecting the
+    // object file format.
+    //
+    // Note: NetBSD doesn't particularly care about the vendor
+    // portion of the name.  We always set it to "unknown".
+    let machine_arch = command_stdout(probe, "uname", &["-p"])
+        .or_else(|| command_stdout(probe, "/sbin/sysctl", &["-n", "hw.machine_arch"]))
+        .or_else(|| command_stdout(probe, "/usr/sbin/sysctl", &["-n", "hw.machine_arch"]))
+        .unwrap_or_else(|| "unknown".into());
+
+    let machine = match machine_arch.as_str() {
+        "aarch64eb" => "aarch64_be-unknown".into(),
+        "armeb" => "armeb-unknown".into(),
+        s if s.starts_with("arm") => "arm-unknown".into(),
+        "sh3el" => "shl-unknown".into(),
+        "sh3eb" => "sh-unknown".into(),
+        "sh5el" => "sh5le-unknown".into(),
+        s if s.starts_with("earmv") => {
+            let arch = match s.get(1..6) {
+                Some(arch) if arch.ends_with(|c: char| c.is_ascii_digit()) => arch,
+                _ => s,
+            };
+            let endian = if s.ends_with("eb") { "eb" } else { "" };
+            format!("{arch}{endian}-unknown")
+        }
+        s => format!("{s}-unknown"),
+    };
+
+    // The Operating System including object format, if it has switched
+    // to ELF recently (or will in the future) and ABI.
+    let os = match machine_arch.as_str() {
+        s if s.starts_with("earm") => "netbsdelf",
+        s if s.starts_with("arm")
+            || s == "i386"
+            || s == "m68k"
+            || s == "ns32k"
+            || s.starts_with("sh3")
+            || s == "sparc"
+            || s == "vax" =>
+        {
+            if probe.preprocess("__ELF__\n").is_some_and(|output| output.contains("__ELF__")) {
+                // Once all utilities can be ECOFF (netbsdecoff) or a.out (netbsdaout).
+                // Return netbsd for either.  FIX?
+                "netbsd"
+            } else {
+                "netbsdelf"
+            }
+        }
+        _ => "netbsd",
+    };
+
+    // Determine ABI tags.
+    let abi = match machine_arch.as_str() {
+        s if s.starts_with("earm") => {
+            let abi = match s.get(..6) {
+                Some(prefix) if prefix.starts_with("earmv") && prefix.ends_with(|c: char| c.is_ascii_digit()) => {
+                    format!("-eabi{}", &s[6..])
+                }
+                _ => s.into(),
+            };
+            abi.strip_suffix("eb").map(Into::into).unwrap_or(abi)
+        }
+        _ => "".into(),
+    };
+
+    // The OS release
+    // Debian GNU/NetBSD machines have a different userland, and
+    // thus, need a distinct triplet. However, they do not need
+    // kernel version information, so it can be replaced with a
+    // suitable tag, in the style of linux-gnu.
+    let release = if uname.version.starts_with("Debian") {
+        "-gnu".into()
+    } else {
+        let release = uname.release.split(['-', '_']).next().unwrap_or_default();
+        release.split('.').take(2).collect::<Vec<_>>().join(".")
+    };
+
+    // Since CPU_TYPE-MANUFACTURER-KERNEL-OPERATING_SYSTEM:
+    // contains redundant information, the shorter form:
+    // CPU_TYPE-MANUFACTURER-OPERATING_SYSTEM is used.
+    format!("{machine}-{os}{release}{abi}")
+}
+
+/// `arch | sed 's/PREFIX.//'`: the machine architecture without the
+/// `PREFIX.` (any one character after it) that `arch` puts in front.
+pub(crate) fn arch(probe: &dyn SystemProbe, prefix: &str) -> String {
+    let arch = command_stdout(probe, "arch", &[]).unwrap_or_default();
+    match arch.find(prefix) {
+        Some(start) => {
+            let rest = &arch[start + prefix.len()..];
+            match rest.chars().next() {
+                Some(c) => format!("{}{}", &arch[..start], &rest[c.len_utf8()..]),
+                None => arch,
+            }
+        }
+        None => arch,
+    }
+}
+
+pub(crate) fn libertybsd(probe: &dyn SystemProbe, uname: &Uname) -> String {
+    let arch = command_stdout(probe, "arch", &[]).unwrap_or_default();
+    let arch = arch.rsplit_once("BSD.").map_or(arch.as_str(), |(_, arch)| arch);
+    format!("{arch}-unknown-libertybsd{}", uname.release)
+}
+
+pub(crate) fn arm_freebsd(probe: &dyn SystemProbe, uname: &Uname) -> String {
+    let processor = command_stdout(probe, "uname", &["-p"]).unwrap_or_default();
+    let release = release_number(&uname.release);
+    if probe.preprocess("__ARM_PCS_VFP\n").is_some_and(|output| output.contains("__ARM_PCS_VFP")) {
+        format!("{processor}-unknown-freebsd{release}-gnueabi")
+    } else {
+        format!("{processor}-unknown-freebsd{release}-gnueabihf")
+    }
+}
+
+pub(crate) fn freebsd(probe: &dyn SystemProbe, uname: &Uname) -> String {
+    let processor = command_stdout(probe, "uname", &["-p"]).unwrap_or_default();
+    let processor = match processor.as_str() {
+        "amd64" => "x86_64",
+        "i386" => "i586",
+        s => s,
+    };
+    format!("{processor}-unknown-freebsd{}", release_number(&uname.release))
+}
+
+pub(crate) fn dragonfly(uname: &Uname) -> String {
+    format!("{}-unknown-dragonfly{}", uname.machine, release_number(&uname.release))
+}
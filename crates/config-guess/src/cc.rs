@@ -0,0 +1,181 @@
+use std::{
+    env::{var, var_os},
+    ffi::OsString,
+    fs::{self, File},
+    hash::{BuildHasher, RandomState},
+    io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use crate::{CommandOutput, host::attbin_path};
+
+/// How long a single compiler run may take before it is killed.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The compiler config.guess uses to learn about the system it runs on,
+/// along with the private directory it works in.
+///
+/// This is `set_cc_for_build`: `CC_FOR_BUILD` wins, then the deprecated
+/// `HOST_CC`, then `CC`, and otherwise the first of `cc gcc c17 c99 c89`
+/// that can compile a trivial file. The directory is removed on drop.
+#[derive(Debug)]
+pub struct BuildCompiler {
+    command: Option<Vec<String>>,
+    path: Option<OsString>,
+    dir: PathBuf,
+}
+
+impl BuildCompiler {
+    pub fn from_env() -> io::Result<Self> {
+        let tmpdir = var_os("TMPDIR").map(PathBuf::from).unwrap_or_else(|| "/tmp".into());
+        let mut compiler = Self {
+            command: None,
+            path: attbin_path(),
+            dir: create_private_dir(&tmpdir)?,
+        };
+
+        let env = |name| var(name).ok().filter(|value| !value.is_empty());
+        let command = match (env("CC_FOR_BUILD"), env("HOST_CC"), env("CC")) {
+            (Some(cc), _, _) | (None, Some(cc), _) | (None, None, Some(cc)) => Some(cc),
+            (None, None, None) => {
+                fs::write(compiler.dummy("c"), "int x;\n")?;
+                let mut found = None;
+                for driver in ["cc", "gcc", "c17", "c99", "c89"] {
+                    compiler.command = Some(vec![driver.into()]);
+                    if compiler.compile(&["-c", "-o", "dummy.o", "dummy.c"]) == Some(true) {
+                        found = Some(driver.into());
+                        break;
+                    }
+                }
+                found
+            }
+        };
+        compiler.command = command.map(|cc| cc.split_whitespace().map(String::from).collect());
+        Ok(compiler)
+    }
+
+    /// The compiler command line, or `None` if no compiler was found.
+    pub fn command(&self) -> Option<&[String]> {
+        self.command.as_deref()
+    }
+
+    /// Run `source` through the preprocessor and return what it printed,
+    /// whether or not it succeeded.
+    pub fn preprocess(&self, source: &str) -> Option<String> {
+        self.preprocess_with_flags(source, &[])
+    }
+
+    /// Like [`preprocess`](Self::preprocess), with `flags` placed before `-E`.
+    pub fn preprocess_with_flags(&self, source: &str, flags: &[&str]) -> Option<String> {
+        fs::write(self.dummy("c"), source).ok()?;
+        self.compile(&[flags, &["-E", "dummy.c"]].concat())?;
+        fs::read(self.dummy("out")).ok().map(|out| String::from_utf8_lossy(&out).into())
+    }
+
+    /// Build `source` into a program and run it, or return `None` if there
+    /// is no compiler or the program does not build.
+    pub fn compile_and_run(&self, source: &str) -> Option<CommandOutput> {
+        fs::write(self.dummy("c"), source).ok()?;
+        if !self.compile(&["-o", "dummy", "dummy.c"])? {
+            return None;
+        }
+        let success = self.run(Command::new(self.dummy("")))?;
+        let stdout = fs::read(self.dummy("out")).ok()?;
+        Some(CommandOutput {
+            success,
+            stdout: String::from_utf8_lossy(&stdout).into(),
+            stderr: String::new(),
+        })
+    }
+
+    fn dummy(&self, extension: &str) -> PathBuf {
+        self.dir.join("dummy").with_extension(extension)
+    }
+
+    /// Run the compiler with `args` inside the private directory, sending
+    /// its standard output to `dummy.out`. Returns whether it succeeded, or
+    /// `None` if it could not be run or timed out.
+    fn compile(&self, args: &[&str]) -> Option<bool> {
+        let (program, flags) = self.command.as_deref()?.split_first()?;
+        let mut command = Command::new(program);
+        command.args(flags).args(args).env("CCOPTS", "");
+        if let Some(path) = &self.path {
+            command.env("PATH", path);
+        }
+        self.run(command)
+    }
+
+    /// Run `command` inside the private directory like [`compile`](Self::compile) does.
+    fn run(&self, mut command: Command) -> Option<bool> {
+        command
+            .current_dir(&self.dir)
+            .stdin(Stdio::null())
+            .stdout(File::create(self.dummy("out")).ok()?)
+            .stderr(Stdio::null());
+
+        let mut child = command.spawn().ok()?;
+        let start = Instant::now();
+        loop {
+            if let Some(status) = child.try_wait().ok()? {
+                return Some(status.success());
+            }
+            if start.elapsed() > TIMEOUT {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+            sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+impl Drop for BuildCompiler {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Create a `cgXXXXXX` directory under `tmpdir` that only we can access,
+/// like `(umask 077 && mktemp -d "$TMPDIR/cgXXXXXX")`.
+fn create_private_dir(tmpdir: &Path) -> io::Result<PathBuf> {
+    const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+    let random = RandomState::new();
+    for attempt in 0u32..100 {
+        let mut n = random.hash_one(attempt);
+        let suffix: String = (0..6)
+            .map(|_| {
+                let c = CHARS[(n % CHARS.len() as u64) as usize] as char;
+                n /= CHARS.len() as u64;
+                c
+            })
+            .collect();
+        let dir = tmpdir.join(format!("cg{suffix}"));
+        let mut builder = fs::DirBuilder::new();
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+        match builder.create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(error) if error.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(error) => return Err(error),
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        format!("cannot create a temporary directory in {}", tmpdir.display()),
+    ))
+}
+
+/// The `KEY=value` assignments in preprocessor output, with spaces removed
+/// the way config.guess does with `sed 's, ,,g'` before `eval`ing them.
+pub fn parse_vars(output: &str) -> impl Iterator<Item = (String, String)> + '_ {
+    output.lines().filter_map(|line| {
+        let line = line.replace(' ', "");
+        let (key, value) = line.split_once('=')?;
+        let is_key = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        is_key.then(|| (key.into(), value.into()))
+    })
+}
@@ -0,0 +1,24 @@
+use crate::Uname;
+
+/// The UNICOS release with its last component replaced by `X`, e.g.
+/// `10.0.0.X` for `10.0.0.2`.
+pub(crate) fn release(release: &str) -> String {
+    match release.rfind('.') {
+        Some(dot) => format!("{}.X", &release[..dot]),
+        None => release.into(),
+    }
+}
+
+/// The C90, J90 and T90 family, named after the machine model.
+pub(crate) fn x90(uname: &Uname) -> String {
+    let mut guess = format!("{}-cray-unicos{}", uname.machine, uname.release);
+    if let Some(start) = guess.find("CRAY") {
+        let model = (start + 4..guess.len().saturating_sub(2))
+            .rev()
+            .find(|&i| guess.as_bytes()[i].is_ascii_uppercase() && &guess.as_bytes()[i + 1..i + 3] == b"90");
+        if let Some(model) = model {
+            guess.replace_range(start..model, "");
+        }
+    }
+    release(&guess.to_ascii_lowercase())
+}
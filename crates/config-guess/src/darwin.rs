@@ -0,0 +1,45 @@
+use crate::{SystemProbe, Uname, command_stdout};
+
+pub(crate) fn darwin(probe: &dyn SystemProbe, uname: &Uname) -> String {
+    // An x86_64 process running under Rosetta sees the x86_64 world, but
+    // the machine is really arm64, as `arm64:Darwin:*:*` would report.
+    if command_stdout(probe, "sysctl", &["-n", "sysctl.proc_translated"]).is_some_and(|translated| translated == "1") {
+        return format!("aarch64-apple-darwin{}", uname.release);
+    }
+
+    let mut processor = command_stdout(probe, "uname", &["-p"]).unwrap_or_default();
+    if processor == "unknown" {
+        processor = "powerpc".into();
+    }
+
+    // Avoid executing cc if there is no toolchain installed as
+    // cc will be a stub that puts up a graphical alert
+    // prompting the user to install developer tools.
+    let has_toolchain = probe
+        .command_output("xcode-select", &["--print-path"])
+        .is_none_or(|output| output.success);
+    let defines = |name: &str, token: &str| {
+        let source = format!("#ifdef {name}\n{token}\n#endif\n");
+        has_toolchain.then(|| probe.preprocess(&source)).flatten().map(|output| output.contains(token))
+    };
+
+    match defines("__LP64__", "IS_64BIT_ARCH") {
+        Some(is_64bit) => {
+            if is_64bit {
+                match processor.as_str() {
+                    "i386" => processor = "x86_64".into(),
+                    "powerpc" => processor = "powerpc64".into(),
+                    _ => {}
+                }
+            }
+            // On 10.4-10.6 one might compile for PowerPC via gcc -arch ppc
+            if defines("__POWERPC__", "IS_PPC") == Some(true) {
+                processor = "powerpc".into();
+            }
+        }
+        // uname -m returns i386 or x86_64
+        None if processor == "i386" => processor = uname.machine.clone(),
+        None => {}
+    }
+    format!("{processor}-apple-darwin{}", uname.release)
+}
@@ -0,0 +1,109 @@
+use crate::SystemProbe;
+
+pub(crate) const EM_386: u16 = 3;
+pub(crate) const EM_ARM: u16 = 40;
+pub(crate) const EM_X86_64: u16 = 62;
+
+const PT_INTERP: u32 = 3;
+const EF_ARM_ABI_FLOAT_HARD: u32 = 0x400;
+
+/// The parts of an ELF executable's headers that tell us about the
+/// userland it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Elf {
+    pub(crate) is_64: bool,
+    pub(crate) machine: u16,
+    pub(crate) flags: u32,
+    /// The dynamic loader from `PT_INTERP`, if there is one.
+    pub(crate) interpreter: Option<String>,
+}
+
+impl Elf {
+    pub(crate) fn parse(bytes: &[u8]) -> Option<Self> {
+        if bytes.get(..4)? != b"\x7fELF" {
+            return None;
+        }
+        let is_64 = match bytes.get(4)? {
+            1 => false,
+            2 => true,
+            _ => return None,
+        };
+        let big_endian = match bytes.get(5)? {
+            1 => false,
+            2 => true,
+            _ => return None,
+        };
+        let read = |offset: usize, size: usize| -> Option<u64> {
+            let field = bytes.get(offset..offset.checked_add(size)?)?;
+            let value = |byte: (usize, &u8)| (*byte.1 as u64) << (8 * byte.0);
+            Some(if big_endian {
+                field.iter().rev().enumerate().map(value).sum()
+            } else {
+                field.iter().enumerate().map(value).sum()
+            })
+        };
+        let word = if is_64 { 8 } else { 4 };
+
+        let machine = read(18, 2)? as u16;
+        let phoff = read(24 + word, word)? as usize;
+        let flags = read(24 + 3 * word, 4)? as u32;
+        let phentsize = read(24 + 3 * word + 6, 2)? as usize;
+        let phnum = read(24 + 3 * word + 8, 2)? as usize;
+
+        let interpreter = (0..phnum).find_map(|i| {
+            let header = phoff.checked_add(i.checked_mul(phentsize)?)?;
+            if read(header, 4)? != PT_INTERP as u64 {
+                return None;
+            }
+            let (offset, size) = if is_64 {
+                (read(header + 8, 8)?, read(header + 32, 8)?)
+            } else {
+                (read(header + 4, 4)?, read(header + 16, 4)?)
+            };
+            let start = offset as usize;
+            let path = bytes.get(start..start.checked_add(size as usize)?)?;
+            let path = path.split(|&b| b == 0).next().unwrap_or_default();
+            Some(String::from_utf8_lossy(path).into_owned())
+        });
+
+        Some(Self {
+            is_64,
+            machine,
+            flags,
+            interpreter,
+        })
+    }
+
+    /// The C library the dynamic loader belongs to, in config.guess's
+    /// `LIBC` spelling.
+    pub(crate) fn libc(&self) -> Option<&'static str> {
+        let interpreter = self.interpreter.as_deref()?;
+        let name = interpreter.rsplit('/').next().unwrap_or(interpreter);
+        if name.starts_with("ld-musl-") {
+            Some("musl")
+        } else if name.starts_with("ld-uClibc") {
+            Some("uclibc")
+        } else if interpreter.starts_with("/system/bin/linker") {
+            Some("android")
+        } else if name.starts_with("ld-linux") || name.starts_with("ld.so") || name.starts_with("ld64.so") {
+            Some("gnu")
+        } else {
+            None
+        }
+    }
+
+    /// Whether a 32-bit ARM executable uses the hard-float calling
+    /// convention.
+    pub(crate) fn is_arm_hard_float(&self) -> bool {
+        self.flags & EF_ARM_ABI_FLOAT_HARD != 0
+            || self.interpreter.as_deref().is_some_and(|interpreter| interpreter.contains("armhf"))
+    }
+}
+
+/// The headers of `/bin/sh`, or failing that of the running executable,
+/// as a stand-in for the userland when there is no compiler to ask.
+pub(crate) fn userland(probe: &dyn SystemProbe) -> Option<Elf> {
+    ["/bin/sh", "/proc/self/exe"]
+        .into_iter()
+        .find_map(|path| Elf::parse(&probe.read_file(path)?))
+}
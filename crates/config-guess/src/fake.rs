@@ -0,0 +1,392 @@
+use std::collections::BTreeMap;
+
+use crate::{CommandOutput, SystemProbe};
+
+/// A [`SystemProbe`] that answers from data held in memory.
+///
+/// The compiler is emulated by evaluating the preprocessor conditionals
+/// config.guess uses against a fixed set of predefined macros, so a whole
+/// system can be described without running anything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FakeProbe {
+    pub uname_machine: Option<String>,
+    pub uname_release: Option<String>,
+    pub uname_system: Option<String>,
+    pub uname_version: Option<String>,
+    /// Paths that exist, with their contents.
+    pub files: BTreeMap<String, Vec<u8>>,
+    /// Commands that can be run, keyed by the program and its arguments
+    /// joined with spaces, e.g. `ldd --version`.
+    pub commands: BTreeMap<String, CommandOutput>,
+    /// Macros the compiler predefines, or `None` if there is no compiler.
+    pub macros: Option<BTreeMap<String, String>>,
+    /// What any program built with the compiler prints when run, or `None`
+    /// if programs do not build.
+    pub program_output: Option<CommandOutput>,
+}
+
+impl SystemProbe for FakeProbe {
+    fn uname_machine(&self) -> Option<String> {
+        self.uname_machine.clone()
+    }
+
+    fn uname_release(&self) -> Option<String> {
+        self.uname_release.clone()
+    }
+
+    fn uname_system(&self) -> Option<String> {
+        self.uname_system.clone()
+    }
+
+    fn uname_version(&self) -> Option<String> {
+        self.uname_version.clone()
+    }
+
+    fn file_exists(&self, path: &str) -> bool {
+        self.files.contains_key(path)
+    }
+
+    fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+        self.files.get(path).cloned()
+    }
+
+    fn glob(&self, pattern: &str) -> Vec<String> {
+        let Ok(pattern) = glob::Pattern::new(pattern) else {
+            return Vec::new();
+        };
+        self.files.keys().filter(|path| pattern.matches(path)).cloned().collect()
+    }
+
+    fn command_output(&self, program: &str, args: &[&str]) -> Option<CommandOutput> {
+        let command = command_line(program, args);
+        self.commands.get(&command).cloned()
+    }
+
+    /// The `flags` are ignored: `macros` describes the compiler as the
+    /// probes invoke it.
+    fn preprocess_with_flags(&self, source: &str, _flags: &[&str]) -> Option<String> {
+        self.macros.as_ref().map(|macros| preprocess(source, macros.clone()))
+    }
+
+    fn compile_and_run(&self, _source: &str) -> Option<CommandOutput> {
+        self.macros.as_ref().and(self.program_output.clone())
+    }
+}
+
+fn command_line(program: &str, args: &[&str]) -> String {
+    let mut command = program.to_owned();
+    for arg in args {
+        command.push(' ');
+        command.push_str(arg);
+    }
+    command
+}
+
+/// Evaluate the conditionals in `source`, dropping `#include`s and
+/// replacing object-like macros in the lines that are kept.
+fn preprocess(source: &str, mut macros: BTreeMap<String, String>) -> String {
+    // For each open conditional: whether its parent is active, whether a
+    // branch has been taken, and whether the current branch is active.
+    let mut stack: Vec<(bool, bool, bool)> = Vec::new();
+    let mut output = String::new();
+    for line in source.lines() {
+        let active = stack.last().is_none_or(|&(_, _, active)| active);
+        let Some(directive) = line.trim_start().strip_prefix('#') else {
+            if active {
+                output.push_str(&expand(line, &macros));
+                output.push('\n');
+            }
+            continue;
+        };
+        let directive = directive.trim_start();
+        let (name, rest) = directive
+            .split_once(|c: char| c.is_whitespace() || c == '(')
+            .map(|(name, _)| (name, directive[name.len()..].trim()))
+            .unwrap_or((directive, ""));
+        match name {
+            "if" => {
+                let taken = active && evaluate(rest, &macros) != 0;
+                stack.push((active, taken, taken));
+            }
+            "ifdef" | "ifndef" => {
+                let taken = active && (macros.contains_key(rest) == (name == "ifdef"));
+                stack.push((active, taken, taken));
+            }
+            "elif" => {
+                if let Some((parent, taken, current)) = stack.last_mut() {
+                    *current = *parent && !*taken && evaluate(rest, &macros) != 0;
+                    *taken |= *current;
+                }
+            }
+            "else" => {
+                if let Some((parent, taken, current)) = stack.last_mut() {
+                    *current = *parent && !*taken;
+                    *taken = true;
+                }
+            }
+            "endif" => {
+                stack.pop();
+            }
+            "define" if active => {
+                let (name, value) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                macros.insert(name.into(), value.trim().into());
+            }
+            "undef" if active => {
+                macros.remove(rest);
+            }
+            _ => {}
+        }
+    }
+    output
+}
+
+/// Replace every identifier in `line` that names a macro with its value.
+fn expand(line: &str, macros: &BTreeMap<String, String>) -> String {
+    let mut output = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find(is_identifier_start) {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find(|c: char| !is_identifier_char(c)).unwrap_or(rest.len());
+        let identifier = &rest[..end];
+        output.push_str(macros.get(identifier).map(String::as_str).unwrap_or(identifier));
+        rest = &rest[end..];
+    }
+    output.push_str(rest);
+    output
+}
+
+pub(crate) fn is_identifier_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+pub(crate) fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Number(i64),
+    Identifier(String),
+    Punct(&'static str),
+}
+
+fn tokenize(expression: &str) -> Vec<Token> {
+    const PUNCTS: [&str; 12] = ["&&", "||", "==", "!=", ">=", "<=", "(", ")", "!", ">", "<", ","];
+    let mut tokens = Vec::new();
+    let mut rest = expression.trim();
+    while !rest.is_empty() {
+        if rest.starts_with(is_identifier_start) {
+            let end = rest.find(|c: char| !is_identifier_char(c)).unwrap_or(rest.len());
+            tokens.push(Token::Identifier(rest[..end].into()));
+            rest = &rest[end..];
+        } else if rest.starts_with(|c: char| c.is_ascii_digit()) {
+            let end = rest.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(rest.len());
+            let digits = rest[..end].trim_end_matches(['u', 'U', 'l', 'L']);
+            tokens.push(Token::Number(digits.parse().unwrap_or(0)));
+            rest = &rest[end..];
+        } else if let Some(punct) = PUNCTS.iter().find(|punct| rest.starts_with(**punct)) {
+            tokens.push(Token::Punct(punct));
+            rest = &rest[punct.len()..];
+        } else {
+            // Anything else (a comment, say) ends the expression.
+            break;
+        }
+        rest = rest.trim_start();
+    }
+    tokens
+}
+
+/// Evaluate a `#if` expression. Unknown identifiers are 0, like in C.
+fn evaluate(expression: &str, macros: &BTreeMap<String, String>) -> i64 {
+    evaluate_expanding(expression, macros, &[])
+}
+
+/// Like [`evaluate`], inside the expansion of the macros in `expanding`.
+/// As in C, those are not expanded again, so they count as 0.
+fn evaluate_expanding(expression: &str, macros: &BTreeMap<String, String>, expanding: &[&str]) -> i64 {
+    let tokens = tokenize(expression);
+    let mut parser = Parser {
+        tokens: &tokens,
+        macros,
+        expanding,
+    };
+    parser.or()
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    macros: &'a BTreeMap<String, String>,
+    expanding: &'a [&'a str],
+}
+
+impl Parser<'_> {
+    fn eat(&mut self, punct: &str) -> bool {
+        match self.tokens.first() {
+            Some(Token::Punct(p)) if *p == punct => {
+                self.tokens = &self.tokens[1..];
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn or(&mut self) -> i64 {
+        let mut value = self.and();
+        while self.eat("||") {
+            let rhs = self.and();
+            value = (value != 0 || rhs != 0) as i64;
+        }
+        value
+    }
+
+    fn and(&mut self) -> i64 {
+        let mut value = self.comparison();
+        while self.eat("&&") {
+            let rhs = self.comparison();
+            value = (value != 0 && rhs != 0) as i64;
+        }
+        value
+    }
+
+    fn comparison(&mut self) -> i64 {
+        let lhs = self.unary();
+        for (punct, op) in [
+            ("==", i64::eq as fn(&i64, &i64) -> bool),
+            ("!=", i64::ne),
+            (">=", i64::ge),
+            ("<=", i64::le),
+            (">", i64::gt),
+            ("<", i64::lt),
+        ] {
+            if self.eat(punct) {
+                let rhs = self.unary();
+                return op(&lhs, &rhs) as i64;
+            }
+        }
+        lhs
+    }
+
+    fn unary(&mut self) -> i64 {
+        if self.eat("!") {
+            return (self.unary() == 0) as i64;
+        }
+        if self.eat("(") {
+            let value = self.or();
+            self.eat(")");
+            return value;
+        }
+        match self.tokens.first().cloned() {
+            Some(Token::Number(n)) => {
+                self.tokens = &self.tokens[1..];
+                n
+            }
+            Some(Token::Identifier(identifier)) if identifier == "defined" => {
+                self.tokens = &self.tokens[1..];
+                let parenthesized = self.eat("(");
+                let defined = match self.tokens.first() {
+                    Some(Token::Identifier(name)) => self.macros.contains_key(name),
+                    _ => false,
+                };
+                self.tokens = self.tokens.get(1..).unwrap_or_default();
+                if parenthesized {
+                    self.eat(")");
+                }
+                defined as i64
+            }
+            Some(Token::Identifier(identifier)) => {
+                self.tokens = &self.tokens[1..];
+                if self.expanding.contains(&identifier.as_str()) {
+                    return 0;
+                }
+                let expanding = [self.expanding, &[identifier.as_str()]].concat();
+                self.macros
+                    .get(&identifier)
+                    .map(|value| evaluate_expanding(value, self.macros, &expanding))
+                    .unwrap_or(0)
+            }
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn macros(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|&(name, value)| (name.into(), value.into())).collect()
+    }
+
+    #[test]
+    fn nested_conditionals() {
+        let source = "#if defined A\n#ifdef B\nab\n#elif C > 1\nac\n#else\na\n#endif\n\
+                      #else\n#if 1\nnone\n#endif\n#endif\n";
+        for (defined, expected) in [
+            (&[][..], "none\n"),
+            (&[("A", "")][..], "a\n"),
+            (&[("A", ""), ("B", "")][..], "ab\n"),
+            (&[("A", ""), ("C", "2")][..], "ac\n"),
+            (&[("A", ""), ("C", "1")][..], "a\n"),
+        ] {
+            assert_eq!(preprocess(source, macros(defined)), expected, "{defined:?}");
+        }
+    }
+
+    #[test]
+    fn inactive_branches_ignore_directives() {
+        let source = "#if 0\n#define A 1\n#else\n#undef B\n#endif\nA B\n";
+        assert_eq!(preprocess(source, macros(&[("B", "2")])), "A B\n");
+    }
+
+    #[test]
+    fn define_and_undef() {
+        let source = "#define A 1\nA\n#undef A\nA\n#ifndef A\nundefined\n#endif\n";
+        assert_eq!(preprocess(source, BTreeMap::new()), "1\nA\nundefined\n");
+    }
+
+    #[test]
+    fn defined() {
+        let macros = macros(&[("A", "0")]);
+        for (expression, expected) in [
+            ("defined A", 1),
+            ("defined(A)", 1),
+            ("defined ( A )", 1),
+            ("defined B", 0),
+            ("defined(B)", 0),
+            ("!defined(B)", 1),
+            ("defined A && !A", 1),
+        ] {
+            assert_eq!(evaluate(expression, &macros), expected, "{expression}");
+        }
+    }
+
+    #[test]
+    fn operators() {
+        let macros = macros(&[("MAJOR", "2"), ("MINOR", "17UL"), ("TRUE", "1")]);
+        for (expression, expected) in [
+            ("MAJOR == 2", 1),
+            ("MAJOR != 2", 0),
+            ("MINOR >= 17", 1),
+            ("MINOR > 17", 0),
+            ("MINOR <= 16", 0),
+            ("MINOR < 18", 1),
+            ("MAJOR > 2 || (MAJOR == 2 && MINOR >= 17)", 1),
+            ("MAJOR > 2 || MAJOR == 2 && MINOR >= 18", 0),
+            ("!TRUE || !UNKNOWN", 1),
+            ("!!MINOR", 1),
+            ("UNKNOWN", 0),
+            ("TRUE /* comment */ && 0", 1),
+        ] {
+            assert_eq!(evaluate(expression, &macros), expected, "{expression}");
+        }
+    }
+
+    #[test]
+    fn self_referential_macros() {
+        let macros = macros(&[("A", "A"), ("B", "C"), ("C", "B"), ("D", "!D")]);
+        for (expression, expected) in [("A", 0), ("B", 0), ("C", 0), ("D", 1), ("defined A && !A", 1)] {
+            assert_eq!(evaluate(expression, &macros), expected, "{expression}");
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use std::{cell::RefCell, fmt, str::FromStr};
+
+use crate::{
+    CommandOutput, Error, FakeProbe, SystemProbe, config_guess,
+    fake::{is_identifier_char, is_identifier_start},
+};
+
+/// How much of an ELF file to record: enough for the headers and the
+/// `PT_INTERP` path, without the rest of the executable.
+const ELF_RECORD_LIMIT: usize = 4096;
+
+/// A recorded system and what config.guess is expected to say about it.
+///
+/// Fixtures are text with one record per line, its fields separated by
+/// tabs. Blank lines and lines starting with `#` are ignored.
+///
+/// - `uname-m VALUE`, and likewise `uname-r`, `uname-s` and `uname-v`
+/// - `file PATH CONTENTS` for a file that exists
+/// - `command COMMAND-LINE ok|failed STDOUT STDERR`
+/// - `compiler` if there is a compiler, then `macro NAME VALUE` for each
+///   macro it predefines
+/// - `program ok|failed STDOUT` for what any program it builds prints
+/// - `expect TRIPLE`, or nothing if config.guess should fail
+///
+/// File contents and outputs escape backslashes, tabs, newlines and other
+/// bytes outside printable ASCII as `\\`, `\t`, `\n` and `\xNN`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fixture {
+    pub probe: FakeProbe,
+    pub expected: Option<String>,
+}
+
+impl Fixture {
+    /// Run config.guess against `probe`, noting down everything it asks.
+    ///
+    /// Compiler answers are recorded as the predefined macros `-dM`
+    /// reports, limited to the ones the probes mention.
+    pub fn record(probe: &dyn SystemProbe) -> Self {
+        let recorder = Recorder {
+            inner: probe,
+            probe: RefCell::default(),
+        };
+        let expected = config_guess(&recorder).ok().map(|triple| triple.to_string());
+        Self {
+            probe: recorder.probe.into_inner(),
+            expected,
+        }
+    }
+
+    /// Run config.guess against the recorded system.
+    pub fn replay(&self) -> Result<String, Error> {
+        config_guess(&self.probe).map(|triple| triple.to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFixtureError {
+    pub line: usize,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for ParseFixtureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl std::error::Error for ParseFixtureError {}
+
+impl FromStr for Fixture {
+    type Err = ParseFixtureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fixture = Self::default();
+        for (index, line) in s.lines().enumerate() {
+            let error = |reason| ParseFixtureError { line: index + 1, reason };
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('\t').collect();
+            let text = |field: &str| unescape(field).map(|bytes| String::from_utf8_lossy(&bytes).into_owned());
+            let probe = &mut fixture.probe;
+            match fields[..] {
+                ["uname-m", value] => probe.uname_machine = Some(value.into()),
+                ["uname-r", value] => probe.uname_release = Some(value.into()),
+                ["uname-s", value] => probe.uname_system = Some(value.into()),
+                ["uname-v", value] => probe.uname_version = Some(value.into()),
+                ["file", path, contents] => {
+                    let contents = unescape(contents).ok_or_else(|| error("invalid escape"))?;
+                    probe.files.insert(path.into(), contents);
+                }
+                ["command", command, status, stdout, stderr] => {
+                    let output = CommandOutput {
+                        success: parse_status(status).ok_or_else(|| error("status must be ok or failed"))?,
+                        stdout: text(stdout).ok_or_else(|| error("invalid escape"))?,
+                        stderr: text(stderr).ok_or_else(|| error("invalid escape"))?,
+                    };
+                    probe.commands.insert(command.into(), output);
+                }
+                ["compiler"] => {
+                    probe.macros.get_or_insert_default();
+                }
+                ["macro", name, value] => {
+                    probe.macros.get_or_insert_default().insert(name.into(), value.into());
+                }
+                ["program", status, stdout] => {
+                    probe.program_output = Some(CommandOutput {
+                        success: parse_status(status).ok_or_else(|| error("status must be ok or failed"))?,
+                        stdout: text(stdout).ok_or_else(|| error("invalid escape"))?,
+                        stderr: String::new(),
+                    });
+                }
+                ["expect", triple] => fixture.expected = Some(triple.into()),
+                [
+                    "uname-m" | "uname-r" | "uname-s" | "uname-v" | "file" | "command" | "compiler" | "macro"
+                    | "program" | "expect",
+                    ..,
+                ] => return Err(error("wrong number of fields")),
+                _ => return Err(error("unknown record")),
+            }
+        }
+        Ok(fixture)
+    }
+}
+
+impl fmt::Display for Fixture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let probe = &self.probe;
+        let status = |success| if success { "ok" } else { "failed" };
+        for (key, value) in [
+            ("uname-m", &probe.uname_machine),
+            ("uname-r", &probe.uname_release),
+            ("uname-s", &probe.uname_system),
+            ("uname-v", &probe.uname_version),
+        ] {
+            if let Some(value) = value {
+                writeln!(f, "{key}\t{}", value.replace('\t', " "))?;
+            }
+        }
+        for (path, contents) in &probe.files {
+            writeln!(f, "file\t{path}\t{}", escape(contents))?;
+        }
+        for (command, output) in &probe.commands {
+            writeln!(
+                f,
+                "command\t{command}\t{}\t{}\t{}",
+                status(output.success),
+                escape(output.stdout.as_bytes()),
+                escape(output.stderr.as_bytes())
+            )?;
+        }
+        if let Some(macros) = &probe.macros {
+            writeln!(f, "compiler")?;
+            for (name, value) in macros {
+                writeln!(f, "macro\t{name}\t{}", value.replace('\t', " "))?;
+            }
+        }
+        if let Some(output) = &probe.program_output {
+            writeln!(f, "program\t{}\t{}", status(output.success), escape(output.stdout.as_bytes()))?;
+        }
+        if let Some(expected) = &self.expected {
+            writeln!(f, "expect\t{expected}")?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_status(status: &str) -> Option<bool> {
+    match status {
+        "ok" => Some(true),
+        "failed" => Some(false),
+        _ => None,
+    }
+}
+
+fn escape(bytes: &[u8]) -> String {
+    let mut escaped = String::new();
+    for &byte in bytes {
+        match byte {
+            b'\\' => escaped.push_str("\\\\"),
+            b'\t' => escaped.push_str("\\t"),
+            b'\n' => escaped.push_str("\\n"),
+            b' '..=b'~' => escaped.push(byte as char),
+            _ => escaped.push_str(&format!("\\x{byte:02x}")),
+        }
+    }
+    escaped
+}
+
+fn unescape(escaped: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut rest = escaped.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        if byte != b'\\' {
+            bytes.push(byte);
+            continue;
+        }
+        let (&kind, tail) = rest.split_first()?;
+        rest = tail;
+        match kind {
+            b'\\' => bytes.push(b'\\'),
+            b't' => bytes.push(b'\t'),
+            b'n' => bytes.push(b'\n'),
+            b'x' => {
+                let hex = std::str::from_utf8(rest.get(..2)?).ok()?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+                rest = &rest[2..];
+            }
+            _ => return None,
+        }
+    }
+    Some(bytes)
+}
+
+/// A [`SystemProbe`] that passes everything through to another one and
+/// keeps the answers in a [`FakeProbe`].
+struct Recorder<'a> {
+    inner: &'a dyn SystemProbe,
+    probe: RefCell<FakeProbe>,
+}
+
+impl SystemProbe for Recorder<'_> {
+    fn uname_machine(&self) -> Option<String> {
+        let value = self.inner.uname_machine();
+        self.probe.borrow_mut().uname_machine.clone_from(&value);
+        value
+    }
+
+    fn uname_release(&self) -> Option<String> {
+        let value = self.inner.uname_release();
+        self.probe.borrow_mut().uname_release.clone_from(&value);
+        value
+    }
+
+    fn uname_system(&self) -> Option<String> {
+        let value = self.inner.uname_system();
+        self.probe.borrow_mut().uname_system.clone_from(&value);
+        value
+    }
+
+    fn uname_version(&self) -> Option<String> {
+        let value = self.inner.uname_version();
+        self.probe.borrow_mut().uname_version.clone_from(&value);
+        value
+    }
+
+    fn file_exists(&self, path: &str) -> bool {
+        let exists = self.inner.file_exists(path);
+        if exists {
+            self.probe.borrow_mut().files.entry(path.into()).or_default();
+        }
+        exists
+    }
+
+    fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+        let contents = self.inner.read_file(path)?;
+        let mut recorded = contents.as_slice();
+        if recorded.starts_with(b"\x7fELF") {
+            recorded = &recorded[..recorded.len().min(ELF_RECORD_LIMIT)];
+        }
+        self.probe.borrow_mut().files.insert(path.into(), recorded.to_vec());
+        Some(contents)
+    }
+
+    fn glob(&self, pattern: &str) -> Vec<String> {
+        let paths = self.inner.glob(pattern);
+        let mut probe = self.probe.borrow_mut();
+        for path in &paths {
+            probe.files.entry(path.clone()).or_default();
+        }
+        paths
+    }
+
+    fn command_output(&self, program: &str, args: &[&str]) -> Option<CommandOutput> {
+        let output = self.inner.command_output(program, args)?;
+        let command = [program].iter().chain(args).copied().collect::<Vec<_>>().join(" ");
+        self.probe.borrow_mut().commands.insert(command, output.clone());
+        Some(output)
+    }
+
+    fn preprocess_with_flags(&self, source: &str, flags: &[&str]) -> Option<String> {
+        let output = self.inner.preprocess_with_flags(source, flags)?;
+        let mut probe = self.probe.borrow_mut();
+        let macros = probe.macros.get_or_insert_default();
+        let defines = self.inner.preprocess_with_flags(source, &[flags, &["-dM"]].concat()).unwrap_or_default();
+        for define in defines.lines() {
+            let Some(define) = define.strip_prefix("#define ") else {
+                continue;
+            };
+            let (name, value) = define.split_once(' ').unwrap_or((define, ""));
+            if mentions(source, name) {
+                macros.insert(name.into(), value.into());
+            }
+        }
+        Some(output)
+    }
+
+    fn compile_and_run(&self, source: &str) -> Option<CommandOutput> {
+        let output = self.inner.compile_and_run(source)?;
+        let mut probe = self.probe.borrow_mut();
+        probe.macros.get_or_insert_default();
+        probe.program_output = Some(output.clone());
+        Some(output)
+    }
+}
+
+/// Whether `name` appears in `source` as an identifier.
+fn mentions(source: &str, name: &str) -> bool {
+    let mut rest = source;
+    while let Some(start) = rest.find(is_identifier_start) {
+        rest = &rest[start..];
+        let end = rest.find(|c: char| !is_identifier_char(c)).unwrap_or(rest.len());
+        if &rest[..end] == name {
+            return true;
+        }
+        rest = &rest[end..];
+    }
+    false
+}
@@ -0,0 +1,19 @@
+use crate::{Uname, release_number};
+
+/// The GNU system.
+pub(crate) fn hurd(uname: &Uname, libc: &str) -> String {
+    let arch = uname.machine.split(['-', '/']).next().unwrap_or_default();
+    let release = uname.release.split('/').next().unwrap_or_default();
+    format!("{arch}-unknown-{libc}{release}")
+}
+
+/// Other systems with GNU libc and userland, e.g. `GNU/kFreeBSD`.
+pub(crate) fn userland(uname: &Uname, libc: &str) -> String {
+    let system = uname.system.split_once('/').map_or(uname.system.as_str(), |(_, system)| system);
+    format!(
+        "{}-unknown-{}{}-{libc}",
+        uname.machine,
+        system.to_ascii_lowercase(),
+        release_number(&uname.release)
+    )
+}
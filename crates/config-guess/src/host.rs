@@ -0,0 +1,142 @@
+use std::{
+    cell::OnceCell,
+    env::{join_paths, split_paths, var_os},
+    ffi::OsString,
+    fs,
+    iter::once,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use crate::{BuildCompiler, CommandOutput, SystemProbe, Uname, command_stdout};
+
+/// A [`SystemProbe`] that inspects the machine it runs on.
+///
+/// The `uname` fields come from the uname(2) system call where there is
+/// one, and from running `uname` otherwise. The compiler is only looked for
+/// the first time something needs it.
+#[derive(Debug)]
+pub struct HostProbe {
+    path: Option<OsString>,
+    uname: Option<Uname>,
+    compiler: OnceCell<Option<BuildCompiler>>,
+}
+
+impl HostProbe {
+    pub fn new() -> Self {
+        Self {
+            path: attbin_path(),
+            uname: syscall_uname(),
+            compiler: OnceCell::new(),
+        }
+    }
+
+    fn command(&self, program: &str) -> Command {
+        let mut command = Command::new(program);
+        if let Some(path) = &self.path {
+            command.env("PATH", path);
+        }
+        command
+    }
+
+    fn uname(&self, field: impl Fn(&Uname) -> &String, flag: &str) -> Option<String> {
+        match &self.uname {
+            Some(uname) => Some(field(uname).clone()),
+            None => command_stdout(self, "uname", &[flag]),
+        }
+    }
+}
+
+/// `PATH` with `/.attbin` appended, if that is where `uname` lives.
+pub(crate) fn attbin_path() -> Option<OsString> {
+    // This is needed to find uname on a Pyramid OSx when run in the BSD universe.
+    // (ghazi@noc.rutgers.edu 1994-08-24)
+    if !Path::new("/.attbin/uname").is_file() {
+        return None;
+    }
+    let path = var_os("PATH").unwrap_or_default();
+    join_paths(split_paths(&path).chain(once(PathBuf::from("/.attbin")))).ok()
+}
+
+#[cfg(unix)]
+fn syscall_uname() -> Option<Uname> {
+    let info = uname::uname().ok()?;
+    Some(Uname {
+        machine: info.machine,
+        release: info.release,
+        system: info.sysname,
+        version: info.version,
+    })
+}
+
+#[cfg(not(unix))]
+fn syscall_uname() -> Option<Uname> {
+    None
+}
+
+impl Default for HostProbe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemProbe for HostProbe {
+    fn uname_machine(&self) -> Option<String> {
+        self.uname(|uname| &uname.machine, "-m")
+    }
+
+    fn uname_release(&self) -> Option<String> {
+        self.uname(|uname| &uname.release, "-r")
+    }
+
+    fn uname_system(&self) -> Option<String> {
+        self.uname(|uname| &uname.system, "-s")
+    }
+
+    fn uname_version(&self) -> Option<String> {
+        self.uname(|uname| &uname.version, "-v")
+    }
+
+    fn file_exists(&self, path: &str) -> bool {
+        Path::new(path).exists()
+    }
+
+    fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+        fs::read(path).ok()
+    }
+
+    fn glob(&self, pattern: &str) -> Vec<String> {
+        let Ok(paths) = glob::glob(pattern) else {
+            return Vec::new();
+        };
+        paths.flatten().map(|path| path.to_string_lossy().into_owned()).collect()
+    }
+
+    fn command_output(&self, program: &str, args: &[&str]) -> Option<CommandOutput> {
+        let output = self.command(program).args(args).stdin(Stdio::null()).output().ok()?;
+        Some(CommandOutput {
+            success: output.status.success(),
+            stdout: String::from_utf8_lossy(&output.stdout).into(),
+            stderr: String::from_utf8_lossy(&output.stderr).into(),
+        })
+    }
+
+    fn preprocess_with_flags(&self, source: &str, flags: &[&str]) -> Option<String> {
+        self.compiler
+            .get_or_init(|| BuildCompiler::from_env().ok())
+            .as_ref()?
+            .preprocess_with_flags(source, flags)
+    }
+
+    fn compile_and_run(&self, source: &str) -> Option<CommandOutput> {
+        self.compiler
+            .get_or_init(|| BuildCompiler::from_env().ok())
+            .as_ref()?
+            .compile_and_run(source)
+    }
+
+    fn compiler(&self) -> Option<Vec<String>> {
+        let compiler = self.compiler.get_or_init(|| BuildCompiler::from_env().ok()).as_ref()?;
+        compiler.command().map(<[String]>::to_vec)
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{SystemProbe, Uname, command_stdout};
+
+const HP_ARCH_SOURCE: &str = "
+#define _HPUX_SOURCE
+#include <stdlib.h>
+#include <unistd.h>
+
+int
+main ()
+{
+#if defined(_SC_KERNEL_BITS)
+    long bits = sysconf(_SC_KERNEL_BITS);
+#endif
+    long cpu  = sysconf (_SC_CPU_VERSION);
+
+    switch (cpu)
+	{
+	case CPU_PA_RISC1_0: puts (\"hppa1.0\"); break;
+	case CPU_PA_RISC1_1: puts (\"hppa1.1\"); break;
+	case CPU_PA_RISC2_0:
+#if defined(_SC_KERNEL_BITS)
+	    switch (bits)
+		{
+		case 64: puts (\"hppa2.0w\"); break;
+		case 32: puts (\"hppa2.0n\"); break;
+		default: puts (\"hppa2.0\"); break;
+		} break;
+#else  /* !defined(_SC_KERNEL_BITS) */
+	    puts (\"hppa2.0\"); break;
+#endif
+	default: puts (\"hppa1.0\"); break;
+	}
+    exit (0);
+}
+";
+
+pub(crate) fn hp9000(probe: &dyn SystemProbe, uname: &Uname) -> String {
+    let mut arch = match uname.machine.as_str() {
+        s if matches_glob!(s, "9000/31?") => "m68000".into(),
+        s if matches_glob!(s, "9000/[34]??") => "m68k".into(),
+        s if matches_glob!(s, "9000/[678][0-9][0-9]") => pa_risc_arch(probe),
+        _ => String::new(),
+    };
+    if arch == "hppa2.0w" {
+        // hppa2.0w-hp-hpux* has a 64-bit kernel and a compiler generating
+        // 32-bit code.  hppa64-hp-hpux* has the same kernel and a compiler
+        // generating 64-bit code.  GNU and HP use different nomenclature:
+        //
+        // $ CC_FOR_BUILD=cc ./config.guess
+        // => hppa2.0w-hp-hpux11.23
+        // $ CC_FOR_BUILD="cc +DA2.0w" ./config.guess
+        // => hppa64-hp-hpux11.23
+        if !probe.preprocess("__LP64__\n").is_some_and(|output| output.contains("__LP64__")) {
+            arch = "hppa64".into();
+        }
+    }
+    format!("{arch}-hp-hpux{}", release(&uname.release))
+}
+
+/// The PA-RISC level from `getconf`, or failing that from a program that
+/// asks `sysconf`.
+fn pa_risc_arch(probe: &dyn SystemProbe) -> String {
+    if probe.file_exists("/usr/bin/getconf") {
+        let getconf = |name| command_stdout(probe, "/usr/bin/getconf", &[name]).unwrap_or_default();
+        let arch = match getconf("SC_CPU_VERSION").as_str() {
+            "523" => "hppa1.0",   // CPU_PA_RISC1_0
+            "528" => "hppa1.1",   // CPU_PA_RISC1_1
+            "532" => match getconf("SC_KERNEL_BITS").as_str() {
+                // CPU_PA_RISC2_0
+                "32" => "hppa2.0n",
+                "64" => "hppa2.0w",
+                "" => "hppa2.0", // HP-UX 10.20
+                _ => "",
+            },
+            _ => "",
+        };
+        if !arch.is_empty() {
+            return arch.into();
+        }
+    }
+    let arch = probe
+        .compile_and_run(HP_ARCH_SOURCE)
+        .map(|output| output.stdout.trim_end_matches('\n').to_owned())
+        .unwrap_or_default();
+    if arch.is_empty() { "hppa".into() } else { arch }
+}
+
+/// `sed -e 's/[^.]*.[0B]*//'`, e.g. `11.23` for `B.11.23`.
+pub(crate) fn release(release: &str) -> &str {
+    match release.find('.') {
+        Some(dot) => release[dot + 1..].trim_start_matches(['0', 'B']),
+        None => "",
+    }
+}
@@ -0,0 +1,346 @@
+use std::{collections::BTreeMap, fmt};
+
+use config_sub::Triple;
+
+macro_rules! matches_glob {
+    ($input:expr, $pattern:expr) => {
+        ::glob::Pattern::new($pattern).expect("pattern should be valid").matches($input)
+    };
+}
+
+mod aix;
+mod bsd;
+mod cc;
+mod cray;
+mod darwin;
+mod elf;
+mod fake;
+mod fixture;
+mod gnu;
+mod host;
+mod hpux;
+mod libc;
+mod linux;
+mod osf1;
+mod probe;
+mod report;
+mod sunos;
+mod sysroot;
+mod windows;
+
+pub use cc::{BuildCompiler, parse_vars};
+pub use fake::FakeProbe;
+pub use fixture::{Fixture, ParseFixtureError};
+pub use host::HostProbe;
+pub use libc::{Libc, LibcSource};
+pub use probe::{CommandOutput, SystemProbe, UnameOverride};
+pub use report::{Abi, Report};
+pub use sysroot::SysrootProbe;
+
+/// The `uname` fields config.guess works from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Uname {
+    pub machine: String,
+    pub release: String,
+    pub system: String,
+    pub version: String,
+}
+
+impl Uname {
+    /// Read the fields from `probe`, using `unknown` for any it cannot tell.
+    pub fn from_probe(probe: &dyn SystemProbe) -> Self {
+        let or_unknown = |field: Option<String>| field.unwrap_or_else(|| "unknown".into());
+        Self {
+            machine: or_unknown(probe.uname_machine()),
+            release: or_unknown(probe.uname_release()),
+            system: or_unknown(probe.uname_system()),
+            version: or_unknown(probe.uname_version()),
+        }
+    }
+}
+
+/// The `MACHINE:SYSTEM:RELEASE:VERSION` form config.guess matches against.
+impl fmt::Display for Uname {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}:{}", self.machine, self.system, self.release, self.version)
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    UnableToGuessSystemType(Uname),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnableToGuessSystemType(_) => write!(f, "unable to guess system type"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Guess the configuration name of the system `probe` looks at.
+///
+/// The result is not canonicalized; pass it through
+/// [`config_sub`](config_sub::config_sub) for that.
+pub fn config_guess(probe: &dyn SystemProbe) -> Result<Triple, Error> {
+    let Guess { uname, triple, .. } = guess(probe);
+    triple.ok_or(Error::UnableToGuessSystemType(uname))
+}
+
+/// A guess, along with what it was based on.
+struct Guess {
+    uname: Uname,
+    libc: Option<Libc>,
+    abi: Option<Abi>,
+    /// The guess, or `None` if config.guess could not make one.
+    triple: Option<Triple>,
+}
+
+fn guess(probe: &dyn SystemProbe) -> Guess {
+    let uname = Uname::from_probe(probe);
+    let libc = detect_libc(probe, &uname);
+    let abi = linux::abi(probe, &uname);
+    let triple = guess_triple(probe, &uname, libc.as_ref().map_or("", |libc| &libc.name), abi.as_ref())
+        .map(|guess| Triple::from_fields(&guess));
+    Guess {
+        uname,
+        libc,
+        abi,
+        triple,
+    }
+}
+
+/// The C library of a Linux or GNU system, or `None` for other systems.
+fn detect_libc(probe: &dyn SystemProbe, uname: &Uname) -> Option<Libc> {
+    match uname.system.as_str() {
+        s if s == "Linux" || s == "GNU" || s.starts_with("GNU/") => Some(libc::detect(probe)),
+        _ => None,
+    }
+}
+
+fn guess_triple(probe: &dyn SystemProbe, uname: &Uname, libc: &str, abi: Option<&Abi>) -> Option<String> {
+    // `MACHINE-VENDOR-linux-LIBC`, the shape most Linux branches share.
+    let linux = |vendor: &str| format!("{}-{vendor}-linux-{libc}", uname.machine);
+
+    // Note: order is significant - the case branches are not exclusive.
+    let guess = match uname.to_string().as_str() {
+        s if matches_glob!(s, "*:NetBSD:*:*") => bsd::netbsd(probe, uname),
+        s if matches_glob!(s, "*:Bitrig:*:*") => {
+            format!("{}-unknown-bitrig{}", bsd::arch(probe, "Bitrig"), uname.release)
+        }
+        s if matches_glob!(s, "*:OpenBSD:*:*") => {
+            format!("{}-unknown-openbsd{}", bsd::arch(probe, "OpenBSD"), uname.release)
+        }
+        s if matches_glob!(s, "*:SecBSD:*:*") => {
+            format!("{}-unknown-secbsd{}", bsd::arch(probe, "SecBSD"), uname.release)
+        }
+        s if matches_glob!(s, "*:LibertyBSD:*:*") => bsd::libertybsd(probe, uname),
+        s if matches_glob!(s, "*:MidnightBSD:*:*") => {
+            format!("{}-unknown-midnightbsd{}", uname.machine, uname.release)
+        }
+        s if matches_glob!(s, "*:ekkoBSD:*:*") => format!("{}-unknown-ekkobsd{}", uname.machine, uname.release),
+        s if matches_glob!(s, "*:SolidBSD:*:*") => format!("{}-unknown-solidbsd{}", uname.machine, uname.release),
+        s if matches_glob!(s, "*:OS108:*:*") => format!("{}-unknown-os108_{}", uname.machine, uname.release),
+        s if matches_glob!(s, "macppc:MirBSD:*:*") => format!("powerpc-unknown-mirbsd{}", uname.release),
+        s if matches_glob!(s, "*:MirBSD:*:*") => format!("{}-unknown-mirbsd{}", uname.machine, uname.release),
+        s if matches_glob!(s, "*:Sortix:*:*") => format!("{}-unknown-sortix", uname.machine),
+        s if matches_glob!(s, "*:Twizzler:*:*") => format!("{}-unknown-twizzler", uname.machine),
+        s if matches_glob!(s, "*:Redox:*:*") => format!("{}-unknown-redox", uname.machine),
+        s if matches_glob!(s, "mips:OSF1:*.*") => "mips-dec-osf1".into(),
+        s if matches_glob!(s, "alpha:OSF1:*:*") => osf1::alpha(probe, uname),
+        s if matches_glob!(s, "*:OS/390:*:*") => "i370-ibm-openedition".into(),
+        s if matches_glob!(s, "*:z/VM:*:*") => "s390-ibm-zvmoe".into(),
+        s if matches_glob!(s, "*:OS400:*:*") => "powerpc-ibm-os400".into(),
+        s if matches_glob!(s, "s390x:SunOS:*:*") => {
+            format!("{}-ibm-solaris2{}", uname.machine, sunos::release_suffix(&uname.release))
+        }
+        s if matches_glob!(s, "sun4H:SunOS:5.*:*") => {
+            format!("sparc-hal-solaris2{}", sunos::release_suffix(&uname.release))
+        }
+        s if matches_glob!(s, "sun4*:SunOS:5.*:*") || matches_glob!(s, "tadpole*:SunOS:5.*:*") => {
+            format!("sparc-sun-solaris2{}", sunos::release_suffix(&uname.release))
+        }
+        s if matches_glob!(s, "i86pc:AuroraUX:5.*:*") || matches_glob!(s, "i86xen:AuroraUX:5.*:*") => {
+            format!("i386-pc-auroraux{}", uname.release)
+        }
+        s if matches_glob!(s, "i86pc:SunOS:5.*:*") || matches_glob!(s, "i86xen:SunOS:5.*:*") => {
+            sunos::i86pc(probe, uname)
+        }
+        s if matches_glob!(s, "sun4*:SunOS:6*:*") => {
+            // According to config.sub, this is the proper way to canonicalize
+            // SunOS6.  Hard to guess exactly what SunOS6 will be like, but
+            // it's likely to be more like Solaris than SunOS4.
+            format!("sparc-sun-solaris3{}", sunos::release_suffix(&uname.release))
+        }
+        s if matches_glob!(s, "sun4*:SunOS:*:*") => sunos::sunos4(probe, uname),
+        s if matches_glob!(s, "sun3*:SunOS:*:*") => format!("m68k-sun-sunos{}", uname.release),
+        s if matches_glob!(s, "sun*:*:4.2BSD:*") => sunos::sunos_4_2bsd(probe)?,
+        s if matches_glob!(s, "aushp:SunOS:*:*") => format!("sparc-auspex-sunos{}", uname.release),
+        s if matches_glob!(s, "RISC*:ULTRIX:*:*") => format!("mips-dec-ultrix{}", uname.release),
+        s if matches_glob!(s, "VAX*:ULTRIX*:*:*") => format!("vax-dec-ultrix{}", uname.release),
+        s if matches_glob!(s, "*:IRIX*:*:*") => format!("mips-sgi-irix{}", uname.release.replace('-', "_")),
+        // AIX 2.2.1 or AIX 2.1.1 is RT/PC AIX; uname -m gives an 8 hex-code
+        // CPU id and uname -s gives 'AIX '.
+        s if matches_glob!(s, "????????:AIX?:[12].1:2") => "romp-ibm-aix".into(),
+        s if matches_glob!(s, "i*86:AIX:*:*") => "i386-ibm-aix".into(),
+        s if matches_glob!(s, "ia64:AIX:*:*") => aix::ia64(probe, uname),
+        s if matches_glob!(s, "*:AIX:2:3") => aix::aix3(probe),
+        s if matches_glob!(s, "*:AIX:*:[4567]") => aix::aix4(probe, uname),
+        s if matches_glob!(s, "*:AIX:*:*") => "rs6000-ibm-aix".into(),
+        s if matches_glob!(s, "9000/[34678]??:HP-UX:*:*") => hpux::hp9000(probe, uname),
+        s if matches_glob!(s, "ia64:HP-UX:*:*") => format!("ia64-hp-hpux{}", hpux::release(&uname.release)),
+        s if matches_glob!(s, "CRAY*Y-MP:*:*:*") => format!("ymp-cray-unicos{}", cray::release(&uname.release)),
+        s if matches_glob!(s, "CRAY*[A-Z]90:*:*:*") => cray::x90(uname),
+        s if matches_glob!(s, "CRAY*TS:*:*:*") => format!("t90-cray-unicos{}", cray::release(&uname.release)),
+        s if matches_glob!(s, "CRAY*T3E:*:*:*") => {
+            format!("alphaev5-cray-unicosmk{}", cray::release(&uname.release))
+        }
+        s if matches_glob!(s, "CRAY*SV1:*:*:*") => format!("sv1-cray-unicos{}", cray::release(&uname.release)),
+        s if matches_glob!(s, "*:UNICOS/mp:*:*") => {
+            format!("craynv-cray-unicosmp{}", cray::release(&uname.release))
+        }
+        s if matches_glob!(s, "i*86:BSD/386:*:*")
+            || matches_glob!(s, "i*86:BSD/OS:*:*")
+            || matches_glob!(s, "*:Ascend Embedded/OS:*:*") =>
+        {
+            format!("{}-pc-bsdi{}", uname.machine, uname.release)
+        }
+        s if matches_glob!(s, "sparc*:BSD/OS:*:*") => format!("sparc-unknown-bsdi{}", uname.release),
+        s if matches_glob!(s, "*:BSD/OS:*:*") => format!("{}-unknown-bsdi{}", uname.machine, uname.release),
+        s if matches_glob!(s, "arm:FreeBSD:*:*") => bsd::arm_freebsd(probe, uname),
+        s if matches_glob!(s, "*:FreeBSD:*:*") => bsd::freebsd(probe, uname),
+        s if matches_glob!(s, "i*:CYGWIN*:*") => format!("{}-pc-cygwin", uname.machine),
+        s if matches_glob!(s, "*:MINGW64*:*") => format!("{}-pc-mingw64", uname.machine),
+        s if matches_glob!(s, "*:MINGW*:*") => format!("{}-pc-mingw32", uname.machine),
+        s if matches_glob!(s, "*:MSYS*:*") => format!("{}-pc-msys", uname.machine),
+        s if matches_glob!(s, "i*:PW*:*") => format!("{}-pc-pw32", uname.machine),
+        s if matches_glob!(s, "*:SerenityOS:*:*") => format!("{}-pc-serenity", uname.machine),
+        s if matches_glob!(s, "*:Interix*:*") => windows::interix(uname)?,
+        s if matches_glob!(s, "i*:UWIN*:*") => format!("{}-pc-uwin", uname.machine),
+        s if matches_glob!(s, "amd64:CYGWIN*:*:*") || matches_glob!(s, "x86_64:CYGWIN*:*:*") => {
+            "x86_64-pc-cygwin".into()
+        }
+        s if matches_glob!(s, "prep*:SunOS:5.*:*") => {
+            format!("powerpcle-unknown-solaris2{}", sunos::release_suffix(&uname.release))
+        }
+        s if matches_glob!(s, "*:GNU:*:*") => gnu::hurd(uname, libc),
+        s if matches_glob!(s, "*:GNU/*:*:*") => gnu::userland(uname, libc),
+        s if matches_glob!(s, "x86_64:[Mm]anagarm:*:*") || matches_glob!(s, "i?86:[Mm]anagarm:*:*") => {
+            format!("{}-pc-managarm-mlibc", uname.machine)
+        }
+        s if matches_glob!(s, "*:[Mm]anagarm:*:*") => format!("{}-unknown-managarm-mlibc", uname.machine),
+        s if matches_glob!(s, "*:Minix:*:*") => format!("{}-unknown-minix", uname.machine),
+        s if matches_glob!(s, "aarch64:Linux:*:*") => linux::aarch64(uname, libc, abi),
+        s if matches_glob!(s, "aarch64_be:Linux:*:*") => format!("aarch64_be-unknown-linux-{libc}"),
+        s if matches_glob!(s, "alpha:Linux:*:*") => linux::alpha(probe, uname, libc),
+        s if matches_glob!(s, "arc:Linux:*:*")
+            || matches_glob!(s, "arceb:Linux:*:*")
+            || matches_glob!(s, "arc32:Linux:*:*")
+            || matches_glob!(s, "arc64:Linux:*:*") =>
+        {
+            linux("unknown")
+        }
+        s if matches_glob!(s, "arm*:Linux:*:*") => linux::arm(uname, libc, abi),
+        s if matches_glob!(s, "avr32*:Linux:*:*") => linux("unknown"),
+        s if matches_glob!(s, "cris:Linux:*:*") => linux("axis"),
+        s if matches_glob!(s, "crisv32:Linux:*:*") => linux("axis"),
+        s if matches_glob!(s, "e2k:Linux:*:*") => linux("unknown"),
+        s if matches_glob!(s, "frv:Linux:*:*") => linux("unknown"),
+        s if matches_glob!(s, "hexagon:Linux:*:*") => linux("unknown"),
+        s if matches_glob!(s, "i*86:Linux:*:*") => linux("pc"),
+        s if matches_glob!(s, "ia64:Linux:*:*") => linux("unknown"),
+        s if matches_glob!(s, "k1om:Linux:*:*") => linux("unknown"),
+        s if matches_glob!(s, "kvx:Linux:*:*") => linux("unknown"),
+        s if matches_glob!(s, "kvx:cos:*:*") => format!("{}-unknown-cos", uname.machine),
+        s if matches_glob!(s, "kvx:mbr:*:*") => format!("{}-unknown-mbr", uname.machine),
+        s if matches_glob!(s, "loongarch32:Linux:*:*")
+            || matches_glob!(s, "loongarch64:Linux:*:*") =>
+        {
+            linux("unknown")
+        }
+        s if matches_glob!(s, "m32r*:Linux:*:*") => linux("unknown"),
+        s if matches_glob!(s, "m68*:Linux:*:*") => linux("unknown"),
+        s if matches_glob!(s, "mips:Linux:*:*") || matches_glob!(s, "mips64:Linux:*:*") => linux::mips(probe, libc)?,
+        s if matches_glob!(s, "mips64el:Linux:*:*") => linux("unknown"),
+        s if matches_glob!(s, "openrisc*:Linux:*:*") => format!("or1k-unknown-linux-{libc}"),
+        s if matches_glob!(s, "or32:Linux:*:*") || matches_glob!(s, "or1k*:Linux:*:*") => {
+            linux("unknown")
+        }
+        s if matches_glob!(s, "padre:Linux:*:*") => format!("sparc-unknown-linux-{libc}"),
+        s if matches_glob!(s, "parisc64:Linux:*:*") || matches_glob!(s, "hppa64:Linux:*:*") => {
+            format!("hppa64-unknown-linux-{libc}")
+        }
+        s if matches_glob!(s, "parisc:Linux:*:*") || matches_glob!(s, "hppa:Linux:*:*") => {
+            linux::parisc(probe, libc)
+        }
+        s if matches_glob!(s, "ppc64:Linux:*:*") => format!("powerpc64-unknown-linux-{libc}"),
+        s if matches_glob!(s, "ppc:Linux:*:*") => format!("powerpc-unknown-linux-{libc}"),
+        s if matches_glob!(s, "ppc64le:Linux:*:*") => format!("powerpc64le-unknown-linux-{libc}"),
+        s if matches_glob!(s, "ppcle:Linux:*:*") => format!("powerpcle-unknown-linux-{libc}"),
+        s if matches_glob!(s, "riscv32:Linux:*:*")
+            || matches_glob!(s, "riscv32be:Linux:*:*")
+            || matches_glob!(s, "riscv64:Linux:*:*")
+            || matches_glob!(s, "riscv64be:Linux:*:*") =>
+        {
+            linux("unknown")
+        }
+        s if matches_glob!(s, "s390:Linux:*:*") || matches_glob!(s, "s390x:Linux:*:*") => {
+            linux("ibm")
+        }
+        s if matches_glob!(s, "sh64*:Linux:*:*") => linux("unknown"),
+        s if matches_glob!(s, "sh*:Linux:*:*") => linux("unknown"),
+        s if matches_glob!(s, "sparc:Linux:*:*") || matches_glob!(s, "sparc64:Linux:*:*") => {
+            linux("unknown")
+        }
+        s if matches_glob!(s, "tile*:Linux:*:*") => linux("unknown"),
+        s if matches_glob!(s, "vax:Linux:*:*") => linux("dec"),
+        s if matches_glob!(s, "x86_64:Linux:*:*") => linux::x86_64(uname, libc, abi),
+        s if matches_glob!(s, "xtensa*:Linux:*:*") => linux("unknown"),
+        // BeOS running on hardware made by Be, PPC only.
+        s if matches_glob!(s, "BeBox:BeOS:*:*") => "powerpc-be-beos".into(),
+        // BeOS running on Mac or Mac clone, PPC only.
+        s if matches_glob!(s, "BeMac:BeOS:*:*") => "powerpc-apple-beos".into(),
+        // BeOS running on Intel PC compatible.
+        s if matches_glob!(s, "BePC:BeOS:*:*") => "i586-pc-beos".into(),
+        // Haiku running on Intel PC compatible.
+        s if matches_glob!(s, "BePC:Haiku:*:*") => "i586-pc-haiku".into(),
+        // Haiku running on Apple PowerPC
+        s if matches_glob!(s, "ppc:Haiku:*:*") => "powerpc-apple-haiku".into(),
+        // Haiku modern gcc (not bound by BeOS compat)
+        s if matches_glob!(s, "*:Haiku:*:*") => format!("{}-unknown-haiku", uname.machine),
+        s if matches_glob!(s, "Power*:Rhapsody:*:*") => format!("powerpc-apple-rhapsody{}", uname.release),
+        s if matches_glob!(s, "*:Rhapsody:*:*") => format!("{}-apple-rhapsody{}", uname.machine, uname.release),
+        s if matches_glob!(s, "arm64:Darwin:*:*") => format!("aarch64-apple-darwin{}", uname.release),
+        s if matches_glob!(s, "*:Darwin:*:*") => darwin::darwin(probe, uname),
+        s if matches_glob!(s, "*:DragonFly:*:*") => bsd::dragonfly(uname),
+        s if matches_glob!(s, "x86_64:[Ii]ronclad:*:*") || matches_glob!(s, "i?86:[Ii]ronclad:*:*") => {
+            format!("{}-pc-ironclad-mlibc", uname.machine)
+        }
+        s if matches_glob!(s, "*:[Ii]ronclad:*:*") => format!("{}-unknown-ironclad-mlibc", uname.machine),
+        _ => return None,
+    };
+    Some(guess)
+}
+
+/// The standard output of a command that ran successfully, without its
+/// trailing newline.
+fn command_stdout(probe: &dyn SystemProbe, program: &str, args: &[&str]) -> Option<String> {
+    probe
+        .command_output(program, args)
+        .filter(|output| output.success)
+        .map(|output| output.stdout.trim_end_matches('\n').into())
+}
+
+/// The release up to any `-` or `(` suffix, e.g. `14.1` for `14.1-RELEASE`.
+fn release_number(release: &str) -> &str {
+    release.split(['-', '(']).next().unwrap_or_default()
+}
+
+/// Preprocess `source` and collect the `KEY=value` lines it prints, or
+/// `None` if there is no compiler. Later assignments win, like `eval`.
+fn preprocess_vars(probe: &dyn SystemProbe, source: &str) -> Option<BTreeMap<String, String>> {
+    probe.preprocess(source).map(|output| parse_vars(&output).collect())
+}
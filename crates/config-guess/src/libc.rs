@@ -0,0 +1,118 @@
+use crate::{SystemProbe, elf, preprocess_vars};
+
+const LIBC_SOURCE: &str = r#"#if defined(__ANDROID__)
+LIBC=android
+#else
+#include <features.h>
+#if defined(__UCLIBC__)
+LIBC=uclibc
+#elif defined(__dietlibc__)
+LIBC=dietlibc
+#elif defined(__GLIBC__)
+LIBC=gnu
+#elif defined(__LLVM_LIBC__)
+LIBC=llvm
+#else
+#include <stdarg.h>
+/* First heuristic to detect musl libc.  */
+#ifdef __DEFINED_va_list
+LIBC=musl
+#endif
+#endif
+#endif
+"#;
+
+/// The os-release `ID`s of distributions whose C library is musl.
+const MUSL_DISTRIBUTIONS: &[&str] = &["alpine", "chimera", "postmarketos"];
+
+/// The C library config.guess settled on and how it found out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Libc {
+    pub name: String,
+    pub source: LibcSource,
+}
+
+/// Which of the checks in [`detect`] named the C library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LibcSource {
+    /// The build compiler's predefined macros.
+    Compiler,
+    /// `ldd --version` mentioning musl.
+    Ldd,
+    /// The dynamic loader of the userland's binaries.
+    Elf,
+    /// A `/lib/ld-musl-*.so.1` dynamic loader.
+    MuslLoader,
+    /// A musl distribution in `/etc/os-release`.
+    OsRelease,
+    /// Nothing could tell, so glibc was picked.
+    Fallback,
+}
+
+impl LibcSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LibcSource::Compiler => "compiler",
+            LibcSource::Ldd => "ldd",
+            LibcSource::Elf => "elf",
+            LibcSource::MuslLoader => "musl-loader",
+            LibcSource::OsRelease => "os-release",
+            LibcSource::Fallback => "fallback",
+        }
+    }
+}
+
+/// Work out the C library of a Linux or GNU system.
+pub(crate) fn detect(probe: &dyn SystemProbe) -> Libc {
+    let found = |name: &str, source| Libc {
+        name: name.into(),
+        source,
+    };
+
+    if let Some(value) = preprocess_vars(probe, LIBC_SOURCE).and_then(|mut vars| vars.remove("LIBC")) {
+        return found(&value, LibcSource::Compiler);
+    }
+
+    // Second heuristic to detect musl libc.
+    if probe.command_output("ldd", &["--version"]).is_some_and(|output| {
+            // ldd --version 2>&1 | grep -q ^musl
+            output.stdout.lines().chain(output.stderr.lines()).any(|line| line.starts_with("musl"))
+        })
+    {
+        return found("musl", LibcSource::Ldd);
+    }
+
+    // Without a compiler, the dynamic loader of the userland's binaries
+    // tells glibc, musl, uClibc and bionic apart.
+    if let Some(value) = elf::userland(probe).as_ref().and_then(elf::Elf::libc) {
+        return found(value, LibcSource::Elf);
+    }
+
+    // A musl dynamic loader is only installed where musl is the C library.
+    if !probe.glob("/lib/ld-musl-*.so.1").is_empty() {
+        return found("musl", LibcSource::MuslLoader);
+    }
+
+    // Distributions built on musl say so in os-release.
+    if os_release_is_musl(probe) {
+        return found("musl", LibcSource::OsRelease);
+    }
+
+    // If the system lacks a compiler and we cannot tell from its binaries,
+    // then just pick glibc.
+    found("gnu", LibcSource::Fallback)
+}
+
+/// Whether `/etc/os-release` names a distribution that uses musl, either
+/// as its `ID` or among the ones it is `ID_LIKE`.
+fn os_release_is_musl(probe: &dyn SystemProbe) -> bool {
+    let Some(os_release) = probe.read_file("/etc/os-release") else {
+        return false;
+    };
+    String::from_utf8_lossy(&os_release).lines().any(|line| {
+        let Some(ids) = line.strip_prefix("ID=").or_else(|| line.strip_prefix("ID_LIKE=")) else {
+            return false;
+        };
+        ids.trim_matches(['"', '\'']).split_whitespace().any(|id| MUSL_DISTRIBUTIONS.contains(&id))
+    })
+}
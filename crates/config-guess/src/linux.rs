@@ -0,0 +1,208 @@
+use crate::{
+    Abi, SystemProbe, Uname,
+    elf::{self, EM_386, EM_ARM, EM_X86_64},
+    preprocess_vars,
+};
+
+/// The CPU types the alpha `/proc/cpuinfo` "cpu model" line maps to.
+const ALPHA_CPU_MODELS: &[(&str, &str)] = &[
+    ("EV5", "alphaev5"),
+    ("EV56", "alphaev56"),
+    ("PCA56", "alphapca56"),
+    ("PCA57", "alphapca56"),
+    ("EV6", "alphaev6"),
+    ("EV67", "alphaev67"),
+    ("EV68*", "alphaev68"),
+];
+
+/// The ABI override of a Linux system that has one, e.g. `x32` on
+/// x86_64, or a 32-bit ARM userland on aarch64.
+pub(crate) fn abi(probe: &dyn SystemProbe, uname: &Uname) -> Option<Abi> {
+    let abi = |name: &str, cpu: Option<&str>| Abi {
+        name: name.into(),
+        cpu: cpu.map(Into::into),
+    };
+    match uname.to_string().as_str() {
+        s if matches_glob!(s, "aarch64:Linux:*:*") => match aarch64_abi(probe).as_str() {
+            name @ ("eabi" | "eabihf") => Some(abi(name, Some("armv8l"))),
+            _ => None,
+        },
+        s if matches_glob!(s, "arm*:Linux:*:*") => match arm_abi(probe) {
+            "" => None,
+            name => Some(abi(name, None)),
+        },
+        s if matches_glob!(s, "x86_64:Linux:*:*") => match x86_64_abi(probe).as_str() {
+            "x86" => Some(abi("x86", Some("i686"))),
+            "x32" => Some(abi("x32", None)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The aarch64 guess, given the [`abi`] of its userland.
+pub(crate) fn aarch64(uname: &Uname, libc: &str, abi: Option<&Abi>) -> String {
+    match abi {
+        Some(Abi { name, cpu }) => {
+            format!("{}-unknown-linux-{libc}{name}", cpu.as_deref().unwrap_or(&uname.machine))
+        }
+        None => format!("{}-unknown-linux-{libc}", uname.machine),
+    }
+}
+
+/// `eabi` or `eabihf` for a 32-bit ARM userland, and `64` otherwise.
+fn aarch64_abi(probe: &dyn SystemProbe) -> String {
+    match preprocess_vars(
+        probe,
+        "#ifdef __ARM_EABI__\n#ifdef __ARM_PCS_VFP\nABI=eabihf\n#else\nABI=eabi\n#endif\n#endif\n",
+    ) {
+        Some(mut vars) => vars.remove("ABI").unwrap_or_else(|| "64".into()),
+        // Without a compiler, a 32-bit ARM userland says the same.
+        None => match elf::userland(probe) {
+            Some(elf) if !elf.is_64 && elf.machine == EM_ARM && elf.is_arm_hard_float() => "eabihf".into(),
+            Some(elf) if !elf.is_64 && elf.machine == EM_ARM => "eabi".into(),
+            _ => "64".into(),
+        },
+    }
+}
+
+pub(crate) fn alpha(probe: &dyn SystemProbe, uname: &Uname, libc: &str) -> String {
+    let cpuinfo = probe.read_file("/proc/cpuinfo").unwrap_or_default();
+    let cpu_model = String::from_utf8_lossy(&cpuinfo)
+        .lines()
+        .filter(|line| line.starts_with("cpu model"))
+        .filter_map(|line| line.rsplit_once(": ").map(|(_, model)| model.to_owned()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let machine = ALPHA_CPU_MODELS
+        .iter()
+        .find(|(model, _)| matches_glob!(&cpu_model, model))
+        .map_or(uname.machine.as_str(), |(_, machine)| machine);
+    let objdump = probe.command_output("objdump", &["--private-headers", "/bin/sh"]);
+    let libc = match objdump {
+        Some(output) if output.stdout.contains("ld.so.1") => "gnulibc1",
+        _ => libc,
+    };
+    format!("{machine}-unknown-linux-{libc}")
+}
+
+/// The 32-bit ARM guess, given the [`abi`] of its userland.
+pub(crate) fn arm(uname: &Uname, libc: &str, abi: Option<&Abi>) -> String {
+    format!("{}-unknown-linux-{libc}{}", uname.machine, abi.map_or("", |abi| &abi.name))
+}
+
+/// `eabi` or `eabihf` for an EABI userland, and nothing for the old ABI.
+fn arm_abi(probe: &dyn SystemProbe) -> &'static str {
+    // The name comes back unexpanded when the macro is not defined.
+    let undefined = |name: &str| {
+        probe
+            .preprocess(&format!("{name}\n"))
+            .is_some_and(|output| output.contains(name))
+    };
+    if undefined("__ARM_EABI__") {
+        ""
+    } else if undefined("__ARM_PCS_VFP") {
+        "eabi"
+    } else {
+        "eabihf"
+    }
+}
+
+/// The MIPS guess, or `None` when there is no compiler to ask, in which
+/// case config.guess gives up.
+pub(crate) fn mips(probe: &dyn SystemProbe, libc: &str) -> Option<String> {
+    let is_glibc = if libc == "gnu" { 1 } else { 0 };
+    let source = format!(
+        "#undef CPU
+#undef mips
+#undef mipsel
+#undef mips64
+#undef mips64el
+#if {is_glibc} && defined(_ABI64)
+LIBCABI=gnuabi64
+#else
+#if {is_glibc} && defined(_ABIN32)
+LIBCABI=gnuabin32
+#else
+LIBCABI={libc}
+#endif
+#endif
+
+#if {is_glibc} && defined(__mips64) && defined(__mips_isa_rev) && __mips_isa_rev>=6
+CPU=mipsisa64r6
+#else
+#if {is_glibc} && !defined(__mips64) && defined(__mips_isa_rev) && __mips_isa_rev>=6
+CPU=mipsisa32r6
+#else
+#if defined(__mips64)
+CPU=mips64
+#else
+CPU=mips
+#endif
+#endif
+#endif
+
+#if defined(__MIPSEL__) || defined(__MIPSEL) || defined(_MIPSEL) || defined(MIPSEL)
+MIPS_ENDIAN=el
+#else
+#if defined(__MIPSEB__) || defined(__MIPSEB) || defined(_MIPSEB) || defined(MIPSEB)
+MIPS_ENDIAN=
+#else
+MIPS_ENDIAN=
+#endif
+#endif
+"
+    );
+    let mut vars = preprocess_vars(probe, &source)?;
+    let cpu = vars.remove("CPU").filter(|cpu| !cpu.is_empty())?;
+    let endian = vars.remove("MIPS_ENDIAN").unwrap_or_default();
+    let libcabi = vars.remove("LIBCABI").unwrap_or_default();
+    Some(format!("{cpu}{endian}-unknown-linux-{libcabi}"))
+}
+
+pub(crate) fn parisc(probe: &dyn SystemProbe, libc: &str) -> String {
+    // Look for CPU level
+    let cpuinfo = probe.read_file("/proc/cpuinfo").unwrap_or_default();
+    let cpuinfo = String::from_utf8_lossy(&cpuinfo);
+    let level = cpuinfo
+        .lines()
+        .find(|line| {
+            line.strip_prefix("cpu").is_some_and(|rest| {
+                rest.chars()
+                    .take_while(|c| !c.is_ascii_lowercase())
+                    .any(|c| c == ':')
+            })
+        })
+        .map(|line| line.split(' ').nth(1).unwrap_or(line));
+    let cpu = match level {
+        Some(level) if level.starts_with("PA7") => "hppa1.1",
+        Some(level) if level.starts_with("PA8") => "hppa2.0",
+        _ => "hppa",
+    };
+    format!("{cpu}-unknown-linux-{libc}")
+}
+
+/// The x86_64 guess, given the [`abi`] of its userland.
+pub(crate) fn x86_64(uname: &Uname, libc: &str, abi: Option<&Abi>) -> String {
+    let mut cpu = uname.machine.as_str();
+    let mut libcabi = libc.to_owned();
+    match abi {
+        Some(Abi { name, cpu: Some(abi_cpu) }) if name == "x86" => cpu = abi_cpu,
+        Some(Abi { name, .. }) if name == "x32" => libcabi = format!("{libc}x32"),
+        _ => {}
+    }
+    format!("{cpu}-pc-linux-{libcabi}")
+}
+
+/// `x86` for an i386 userland, `x32` for an x32 one, and `64` otherwise.
+fn x86_64_abi(probe: &dyn SystemProbe) -> String {
+    match preprocess_vars(probe, "#ifdef __i386__\nABI=x86\n#else\n#ifdef __ILP32__\nABI=x32\n#endif\n#endif\n") {
+        Some(mut vars) => vars.remove("ABI").unwrap_or_else(|| "64".into()),
+        // Without a compiler, an i386 or x32 userland says the same.
+        None => match elf::userland(probe) {
+            Some(elf) if !elf.is_64 && elf.machine == EM_386 => "x86".into(),
+            Some(elf) if !elf.is_64 && elf.machine == EM_X86_64 => "x32".into(),
+            _ => "64".into(),
+        },
+    }
+}
@@ -0,0 +1,198 @@
+use config_guess::{Fixture, HostProbe, Report, SysrootProbe, SystemProbe, UnameOverride, config_guess};
+use glob::Pattern;
+use std::{
+    collections::VecDeque,
+    env::{args_os, current_exe},
+    error::Error,
+    ffi::OsString,
+    fs,
+    process::{ExitCode, exit},
+};
+
+const TIMESTAMP: &str = "2025-07-10";
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let current_exe = current_exe()?;
+    let me = current_exe
+        .file_name()
+        .ok_or_else(|| format!("no file name for {:?}", &current_exe))?;
+
+    let usage = format!(
+        r#"Usage: {} [OPTION]...
+
+Output the configuration name of the system '{}' is run on.
+
+Options:
+  -h, --help         print this help, then exit
+  -t, --time-stamp   print date of last modification, then exit
+  -v, --version      print version number, then exit
+      --json         print the uname fields, how the C library and ABI were
+                     detected, the compiler and the guess as JSON
+      --record       print a fixture describing this system, then exit
+      --replay FILE...
+                     check config.guess against the fixtures in FILE...,
+                     then exit
+      --uname-machine VALUE, --uname-system VALUE,
+      --uname-release VALUE, --uname-version VALUE
+                     guess as if 'uname' reported VALUE
+      --sysroot DIR  guess the C library and ABI from the files under DIR,
+                     such as a chroot or container root filesystem
+
+Report bugs and patches to https://github.com/jcbhmr/config-rs."#,
+        current_exe.display(),
+        me.display()
+    );
+
+    let version = format!(
+        r#"GNU config.guess ({})
+
+Originally written by Per Bothner.
+Copyright 1992-2025 Free Software Foundation, Inc.
+Copyright 2025 Jacob Hummer
+
+This is free software; see the source for copying conditions.  There is NO
+warranty; not even for MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE."#,
+        TIMESTAMP
+    );
+
+    let help = format!(r#"Try '{} --help' for more information."#, me.display());
+
+    // Parse command line
+    let mut args_os = args_os().skip(1).collect::<VecDeque<_>>();
+    let mut operands = Vec::new();
+    let mut json = false;
+    let mut record = false;
+    let mut sysroot = None;
+    let (mut uname_machine, mut uname_release, mut uname_system, mut uname_version) = (None, None, None, None);
+    while let Some(arg_os) = args_os.pop_front() {
+        let Some(s) = arg_os.to_str() else {
+            operands.push(arg_os);
+            continue;
+        };
+        let (option, inline_value) = match s.split_once('=') {
+            Some((option, value)) if option.starts_with("--") => (option, Some(OsString::from(value))),
+            _ => (s, None),
+        };
+        let mut value = || match inline_value.clone().or_else(|| args_os.pop_front()) {
+            Some(value) => value,
+            None => {
+                eprintln!("{}: missing argument to {}\n{}", me.display(), option, help);
+                exit(1);
+            }
+        };
+        match option {
+            "--uname-machine" => uname_machine = Some(value().to_string_lossy().into_owned()),
+            "--uname-release" => uname_release = Some(value().to_string_lossy().into_owned()),
+            "--uname-system" => uname_system = Some(value().to_string_lossy().into_owned()),
+            "--uname-version" => uname_version = Some(value().to_string_lossy().into_owned()),
+            "--sysroot" => sysroot = Some(value()),
+            _ if inline_value.is_some() => {
+                eprintln!("{}: invalid option {}\n{}", me.display(), s, help);
+                return Ok(ExitCode::FAILURE);
+            }
+            "-t" => {
+                println!("{}", TIMESTAMP);
+                return Ok(ExitCode::SUCCESS);
+            }
+            _ if Pattern::new("--time*")?.matches(s) => {
+                println!("{}", TIMESTAMP);
+                return Ok(ExitCode::SUCCESS);
+            }
+            "--version" | "-v" => {
+                println!("{}", version);
+                return Ok(ExitCode::SUCCESS);
+            }
+            _ if s == "-h" || Pattern::new("--h*")?.matches(s) => {
+                println!("{}", usage);
+                return Ok(ExitCode::SUCCESS);
+            }
+            "--json" => json = true,
+            "--record" => record = true,
+            "--replay" => {
+                if args_os.is_empty() {
+                    eprintln!("{}: missing argument\n{}", me.display(), help);
+                    return Ok(ExitCode::FAILURE);
+                }
+                return Ok(if replay(args_os) { ExitCode::SUCCESS } else { ExitCode::FAILURE });
+            }
+            "--" => {
+                // Stop option processing
+                operands.extend(args_os.drain(..));
+            }
+            "-" => operands.push(arg_os), // Use stdin as input
+            _ if s.starts_with('-') => {
+                eprintln!("{}: invalid option {}\n{}", me.display(), s, help);
+                return Ok(ExitCode::FAILURE);
+            }
+            _ => operands.push(arg_os),
+        }
+    }
+
+    if !operands.is_empty() {
+        eprintln!("{}: too many arguments\n{}", me.display(), help);
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let base: Box<dyn SystemProbe> = match sysroot {
+        Some(sysroot) => Box::new(SysrootProbe::new(sysroot)),
+        None => Box::new(HostProbe::new()),
+    };
+    let probe = UnameOverride {
+        probe: base.as_ref(),
+        machine: uname_machine,
+        release: uname_release,
+        system: uname_system,
+        version: uname_version,
+    };
+
+    if record {
+        print!("{}", Fixture::record(&probe));
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if json {
+        let report = Report::new(&probe);
+        print!("{}", report.to_json());
+        return Ok(if report.triple.is_some() { ExitCode::SUCCESS } else { ExitCode::FAILURE });
+    }
+
+    // Return rather than exit so the probe's scratch directory is cleaned up.
+    match config_guess(&probe) {
+        Ok(triple) => {
+            println!("{}", triple);
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(error) => {
+            eprintln!("{}: {}", current_exe.display(), error);
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
+
+/// Check config.guess against each fixture, reporting the ones that do not
+/// match. Returns whether they all did.
+fn replay(paths: VecDeque<OsString>) -> bool {
+    let mut all_passed = true;
+    for path in paths {
+        let display = path.to_string_lossy();
+        let fixture = match fs::read_to_string(&path).map_err(|error| error.to_string()).and_then(|text| {
+            text.parse::<Fixture>().map_err(|error| error.to_string())
+        }) {
+            Ok(fixture) => fixture,
+            Err(error) => {
+                eprintln!("{}: {}", display, error);
+                all_passed = false;
+                continue;
+            }
+        };
+        let actual = fixture.replay().ok();
+        if actual == fixture.expected {
+            println!("{}: ok", display);
+        } else {
+            let show = |triple: Option<String>| triple.unwrap_or_else(|| "no guess".into());
+            println!("{}: expected {}, got {}", display, show(fixture.expected), show(actual));
+            all_passed = false;
+        }
+    }
+    all_passed
+}
@@ -0,0 +1,59 @@
+use crate::{SystemProbe, Uname};
+
+/// The CPU types `/usr/sbin/psrinfo -v` reports, and what they map to.
+const ALPHA_CPU_TYPES: &[(&str, &str)] = &[
+    ("EV4 (21064)", "alpha"),
+    ("EV4.5 (21064)", "alpha"),
+    ("LCA4 (21066/21068)", "alpha"),
+    ("EV5 (21164)", "alphaev5"),
+    ("EV5.6 (21164A)", "alphaev56"),
+    ("EV5.6 (21164PC)", "alphapca56"),
+    ("EV5.7 (21164PC)", "alphapca57"),
+    ("EV6 (21264)", "alphaev6"),
+    ("EV6.7 (21264A)", "alphaev67"),
+    ("EV6.8CB (21264C)", "alphaev68"),
+    ("EV6.8AL (21264B)", "alphaev68"),
+    ("EV6.8CX (21264D)", "alphaev68"),
+    ("EV6.9A (21264/EV69A)", "alphaev69"),
+    ("EV7 (21364)", "alphaev7"),
+    ("EV7.9 (21364A)", "alphaev79"),
+];
+
+pub(crate) fn alpha(probe: &dyn SystemProbe, uname: &Uname) -> String {
+    let sizer_field = |field| {
+        let sizer = probe.command_output("/usr/sbin/sizer", &["-v"]).unwrap_or_default();
+        sizer
+            .stdout
+            .lines()
+            .map(|line| line.split_whitespace().nth(field).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let release = match uname.release.as_str() {
+        s if s.ends_with("4.0") => sizer_field(2),
+        s if s.contains("5.") => sizer_field(3),
+        s => s.into(),
+    };
+
+    // According to Compaq, /usr/sbin/psrinfo has been available on
+    // OSF/1 and Tru64 systems produced since 1995.  I hope that
+    // covers most systems running today.  We only detect the type
+    // of CPU 0.
+    let psrinfo = probe.command_output("/usr/sbin/psrinfo", &["-v"]).unwrap_or_default();
+    let cpu_type = psrinfo.stdout.lines().find_map(|line| {
+        let cpu_type = line.strip_prefix("  The alpha ")?;
+        cpu_type.rfind(" processor").map(|end| &cpu_type[..end])
+    });
+    let machine = ALPHA_CPU_TYPES
+        .iter()
+        .find(|(name, _)| Some(*name) == cpu_type)
+        .map_or(uname.machine.as_str(), |(_, machine)| machine);
+
+    // A Pn.n version is a patched version.
+    // A Vn.n version is a released version.
+    // A Tn.n version is a released field test version.
+    // A Xn.n version is an unreleased experimental baselevel.
+    // 1.2 uses "1.2" for uname -r.
+    let release = release.strip_prefix(['P', 'V', 'T', 'X']).unwrap_or(&release);
+    format!("{machine}-dec-osf{}", release.to_ascii_lowercase())
+}
@@ -0,0 +1,128 @@
+/// What a command printed and whether it exited successfully.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Everything config.guess needs to find out about the system it runs on.
+///
+/// [`HostProbe`](crate::HostProbe) asks the machine it runs on, while
+/// [`FakeProbe`](crate::FakeProbe) answers from data held in memory.
+pub trait SystemProbe {
+    /// `uname -m`, or `None` if it cannot be determined.
+    fn uname_machine(&self) -> Option<String>;
+
+    /// `uname -r`, or `None` if it cannot be determined.
+    fn uname_release(&self) -> Option<String>;
+
+    /// `uname -s`, or `None` if it cannot be determined.
+    fn uname_system(&self) -> Option<String>;
+
+    /// `uname -v`, or `None` if it cannot be determined.
+    fn uname_version(&self) -> Option<String>;
+
+    /// Whether anything exists at `path`.
+    fn file_exists(&self, path: &str) -> bool;
+
+    /// The contents of the file at `path`, or `None` if it cannot be read.
+    fn read_file(&self, path: &str) -> Option<Vec<u8>>;
+
+    /// The paths that match the shell glob `pattern`, in sorted order.
+    fn glob(&self, pattern: &str) -> Vec<String>;
+
+    /// Run `program` with `args` and no input, or `None` if it could not be
+    /// run at all.
+    fn command_output(&self, program: &str, args: &[&str]) -> Option<CommandOutput>;
+
+    /// Run C `source` through the build compiler's preprocessor, or `None`
+    /// if there is no compiler to do so.
+    fn preprocess(&self, source: &str) -> Option<String> {
+        self.preprocess_with_flags(source, &[])
+    }
+
+    /// Like [`preprocess`](Self::preprocess), passing `flags` such as
+    /// `-m64` to the compiler as well.
+    fn preprocess_with_flags(&self, source: &str, flags: &[&str]) -> Option<String>;
+
+    /// Build C `source` into a program with the build compiler and run it,
+    /// or `None` if there is no compiler or the program does not build.
+    fn compile_and_run(&self, source: &str) -> Option<CommandOutput>;
+
+    /// The build compiler's command line, or `None` if there is no
+    /// compiler or the probe cannot say.
+    fn compiler(&self) -> Option<Vec<String>> {
+        None
+    }
+}
+
+/// A [`SystemProbe`] that reports the `uname` fields it is given and asks
+/// another probe everything else, to see what config.guess would say on a
+/// different machine.
+pub struct UnameOverride<'a> {
+    pub probe: &'a dyn SystemProbe,
+    pub machine: Option<String>,
+    pub release: Option<String>,
+    pub system: Option<String>,
+    pub version: Option<String>,
+}
+
+impl<'a> UnameOverride<'a> {
+    /// Override nothing yet.
+    pub fn new(probe: &'a dyn SystemProbe) -> Self {
+        Self {
+            probe,
+            machine: None,
+            release: None,
+            system: None,
+            version: None,
+        }
+    }
+}
+
+impl SystemProbe for UnameOverride<'_> {
+    fn uname_machine(&self) -> Option<String> {
+        self.machine.clone().or_else(|| self.probe.uname_machine())
+    }
+
+    fn uname_release(&self) -> Option<String> {
+        self.release.clone().or_else(|| self.probe.uname_release())
+    }
+
+    fn uname_system(&self) -> Option<String> {
+        self.system.clone().or_else(|| self.probe.uname_system())
+    }
+
+    fn uname_version(&self) -> Option<String> {
+        self.version.clone().or_else(|| self.probe.uname_version())
+    }
+
+    fn file_exists(&self, path: &str) -> bool {
+        self.probe.file_exists(path)
+    }
+
+    fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+        self.probe.read_file(path)
+    }
+
+    fn glob(&self, pattern: &str) -> Vec<String> {
+        self.probe.glob(pattern)
+    }
+
+    fn command_output(&self, program: &str, args: &[&str]) -> Option<CommandOutput> {
+        self.probe.command_output(program, args)
+    }
+
+    fn preprocess_with_flags(&self, source: &str, flags: &[&str]) -> Option<String> {
+        self.probe.preprocess_with_flags(source, flags)
+    }
+
+    fn compile_and_run(&self, source: &str) -> Option<CommandOutput> {
+        self.probe.compile_and_run(source)
+    }
+
+    fn compiler(&self) -> Option<Vec<String>> {
+        self.probe.compiler()
+    }
+}
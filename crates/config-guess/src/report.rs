@@ -0,0 +1,114 @@
+use std::fmt::{self, Write};
+
+use config_sub::Triple;
+
+use crate::{Guess, Libc, SystemProbe, Uname, guess};
+
+/// An ABI that changes the triple from what `uname` alone would give.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Abi {
+    /// The ABI, e.g. `x32` or `eabihf`.
+    pub name: String,
+    /// The CPU it puts in the triple instead of `uname -m`, e.g. `armv8l`.
+    pub cpu: Option<String>,
+}
+
+/// What config.guess found out on the way to its answer, for bug reports.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub uname: Uname,
+    /// The C library, on the systems whose triples name one.
+    pub libc: Option<Libc>,
+    /// The build compiler command line, if there is one.
+    pub compiler: Option<Vec<String>>,
+    pub abi: Option<Abi>,
+    /// The guess, or `None` if config.guess could not make one.
+    pub triple: Option<Triple>,
+}
+
+impl Report {
+    /// Guess the configuration name of the system `probe` looks at, like
+    /// [`config_guess`](crate::config_guess), keeping track of how.
+    pub fn new(probe: &dyn SystemProbe) -> Self {
+        let Guess {
+            uname,
+            libc,
+            abi,
+            triple,
+        } = guess(probe);
+        Self {
+            uname,
+            libc,
+            compiler: probe.compiler(),
+            abi,
+            triple,
+        }
+    }
+
+    /// The report as a JSON object.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        // Writing to a String cannot fail.
+        let _ = self.write_json(&mut json);
+        json
+    }
+
+    fn write_json(&self, json: &mut String) -> fmt::Result {
+        let uname = &self.uname;
+        writeln!(json, "{{")?;
+        writeln!(json, "  \"uname\": {{")?;
+        writeln!(json, "    \"machine\": {},", string(&uname.machine))?;
+        writeln!(json, "    \"system\": {},", string(&uname.system))?;
+        writeln!(json, "    \"release\": {},", string(&uname.release))?;
+        writeln!(json, "    \"version\": {}", string(&uname.version))?;
+        writeln!(json, "  }},")?;
+        match &self.libc {
+            Some(libc) => writeln!(
+                json,
+                "  \"libc\": {{ \"name\": {}, \"source\": {} }},",
+                string(&libc.name),
+                string(libc.source.as_str())
+            )?,
+            None => writeln!(json, "  \"libc\": null,")?,
+        }
+        match &self.compiler {
+            Some(command) => {
+                let words = command.iter().map(|word| string(word)).collect::<Vec<_>>();
+                writeln!(json, "  \"compiler\": [{}],", words.join(", "))?;
+            }
+            None => writeln!(json, "  \"compiler\": null,")?,
+        }
+        match &self.abi {
+            Some(abi) => writeln!(
+                json,
+                "  \"abi\": {{ \"name\": {}, \"cpu\": {} }},",
+                string(&abi.name),
+                abi.cpu.as_deref().map_or("null".into(), string)
+            )?,
+            None => writeln!(json, "  \"abi\": null,")?,
+        }
+        match &self.triple {
+            Some(triple) => writeln!(json, "  \"triple\": {}", string(&triple.to_string()))?,
+            None => writeln!(json, "  \"triple\": null")?,
+        }
+        writeln!(json, "}}")
+    }
+}
+
+/// `s` as a JSON string literal.
+fn string(s: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c if c.is_control() => literal.push_str(&format!("\\u{:04x}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
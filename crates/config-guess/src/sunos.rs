@@ -0,0 +1,46 @@
+use crate::{SystemProbe, Uname, command_stdout};
+
+pub(crate) fn i86pc(probe: &dyn SystemProbe, uname: &Uname) -> String {
+    // If there is a compiler, see if it is configured for 64-bit objects.
+    // Note that the Sun cc does not turn __LP64__ into 1 like gcc does.
+    // This test works for both compilers.
+    let is_64bit = match probe.preprocess_with_flags("#ifdef __amd64\nIS_64BIT_ARCH\n#endif\n", &["-m64"]) {
+        Some(output) => output.contains("IS_64BIT_ARCH"),
+        // Without a compiler, ask the kernel which word size it supports.
+        None => command_stdout(probe, "isainfo", &["-b"]).is_some_and(|bits| bits == "64"),
+    };
+    let arch = if is_64bit { "x86_64" } else { "i386" };
+    format!("{arch}-pc-solaris2{}", release_suffix(&uname.release))
+}
+
+pub(crate) fn sunos4(probe: &dyn SystemProbe, uname: &Uname) -> String {
+    let release = match command_stdout(probe, "/usr/bin/arch", &["-k"]) {
+        Some(arch) if arch.starts_with("Series") || arch.starts_with("S4") => uname.version.as_str(),
+        _ => uname.release.as_str(),
+    };
+    // Japanese Language versions have a version number like '4.1.3-JL'.
+    format!("sparc-sun-sunos{}", release.replacen('-', "_", 1))
+}
+
+/// SunOS on 4.2BSD, or `None` if `/bin/arch` is neither `sun3` nor `sun4`.
+pub(crate) fn sunos_4_2bsd(probe: &dyn SystemProbe) -> Option<String> {
+    let motd = probe.read_file("/etc/motd").unwrap_or_default();
+    let release = String::from_utf8_lossy(&motd)
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(4))
+        .map(|field| field.chars().take(3).collect())
+        .filter(|release: &String| !release.is_empty())
+        .unwrap_or_else(|| "3".into());
+    match command_stdout(probe, "/bin/arch", &[])?.as_str() {
+        "sun3" => Some(format!("m68k-sun-sunos{release}")),
+        "sun4" => Some(format!("sparc-sun-sunos{release}")),
+        _ => None,
+    }
+}
+
+/// The release from its first `.` on, e.g. `.11` for `5.11`, which follows
+/// the `solaris2` in the guess.
+pub(crate) fn release_suffix(release: &str) -> &str {
+    release.find('.').map_or("", |dot| &release[dot..])
+}
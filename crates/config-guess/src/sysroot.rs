@@ -0,0 +1,128 @@
+use std::{
+    fs,
+    path::{Component, PathBuf},
+};
+
+use crate::{CommandOutput, HostProbe, SystemProbe};
+
+/// How many symbolic links to follow before giving up, like `MAXSYMLINKS`.
+const MAX_SYMLINKS: usize = 40;
+
+/// A [`SystemProbe`] that looks at a root filesystem, such as a chroot or
+/// an unpacked container image, instead of the system it runs on.
+///
+/// Paths are looked up under the root, with absolute symbolic links kept
+/// inside it. The `uname` fields still come from the running system, since
+/// a root filesystem has no kernel, and nothing is run: the host's commands
+/// and compiler would describe the host. That leaves config.guess to infer
+/// the C library and ABI from the files.
+#[derive(Debug)]
+pub struct SysrootProbe {
+    root: PathBuf,
+    host: HostProbe,
+}
+
+impl SysrootProbe {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            host: HostProbe::new(),
+        }
+    }
+
+    /// Where `path` inside the root lives on the running system.
+    fn resolve(&self, path: &str) -> Option<PathBuf> {
+        let mut resolved = self.root.clone();
+        let mut pending: Vec<PathBuf> = vec![path.into()];
+        let mut symlinks = 0;
+        while let Some(path) = pending.pop() {
+            let mut components = path.components();
+            let Some(component) = components.next() else {
+                continue;
+            };
+            let rest = components.as_path();
+            if !rest.as_os_str().is_empty() {
+                pending.push(rest.into());
+            }
+            match component {
+                Component::RootDir | Component::Prefix(_) => resolved.clone_from(&self.root),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if resolved != self.root {
+                        resolved.pop();
+                    }
+                }
+                Component::Normal(name) => {
+                    resolved.push(name);
+                    if let Ok(target) = fs::read_link(&resolved) {
+                        symlinks += 1;
+                        if symlinks > MAX_SYMLINKS {
+                            return None;
+                        }
+                        resolved.pop();
+                        pending.push(target);
+                    }
+                }
+            }
+        }
+        Some(resolved)
+    }
+}
+
+impl SystemProbe for SysrootProbe {
+    fn uname_machine(&self) -> Option<String> {
+        self.host.uname_machine()
+    }
+
+    fn uname_release(&self) -> Option<String> {
+        self.host.uname_release()
+    }
+
+    fn uname_system(&self) -> Option<String> {
+        self.host.uname_system()
+    }
+
+    fn uname_version(&self) -> Option<String> {
+        self.host.uname_version()
+    }
+
+    fn file_exists(&self, path: &str) -> bool {
+        self.resolve(path).is_some_and(|path| path.exists())
+    }
+
+    fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+        fs::read(self.resolve(path)?).ok()
+    }
+
+    /// Only the last component of `pattern` may contain wildcards.
+    fn glob(&self, pattern: &str) -> Vec<String> {
+        let (dir, name) = pattern.rsplit_once('/').unwrap_or(("", pattern));
+        let (Some(resolved), Ok(name)) = (self.resolve(dir), glob::Pattern::new(name)) else {
+            return Vec::new();
+        };
+        let Ok(entries) = fs::read_dir(resolved) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<String> = entries
+            .flatten()
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|entry| name.matches(entry))
+            .map(|entry| format!("{dir}/{entry}"))
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    fn command_output(&self, _program: &str, _args: &[&str]) -> Option<CommandOutput> {
+        None
+    }
+
+    fn preprocess_with_flags(&self, _source: &str, _flags: &[&str]) -> Option<String> {
+        None
+    }
+
+    fn compile_and_run(&self, _source: &str) -> Option<CommandOutput> {
+        None
+    }
+}
+
@@ -0,0 +1,12 @@
+use crate::Uname;
+
+/// Interix, or `None` for a machine it does not run on.
+pub(crate) fn interix(uname: &Uname) -> Option<String> {
+    let cpu = match uname.machine.as_str() {
+        "x86" => "i586-pc",
+        "authenticamd" | "genuineintel" | "EM64T" => "x86_64-unknown",
+        "IA64" => "ia64-unknown",
+        _ => return None,
+    };
+    Some(format!("{cpu}-interix{}", uname.release))
+}
@@ -0,0 +1,35 @@
+use std::{fs, path::Path};
+
+use config_guess::Fixture;
+
+/// Replay every fixture under `fixtures/` and check config.guess still
+/// gives the triple it records.
+#[test]
+fn fixtures() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+    let mut paths = fs::read_dir(&dir)
+        .expect("fixtures directory should be readable")
+        .map(|entry| entry.expect("fixtures directory should be readable").path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .collect::<Vec<_>>();
+    paths.sort();
+    assert!(!paths.is_empty(), "no fixtures in {}", dir.display());
+
+    let mut failures = Vec::new();
+    for path in &paths {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let text = fs::read_to_string(path).expect("fixture should be readable");
+        let fixture = match text.parse::<Fixture>() {
+            Ok(fixture) => fixture,
+            Err(error) => {
+                failures.push(format!("{name}: {error}"));
+                continue;
+            }
+        };
+        let actual = fixture.replay().ok();
+        if actual != fixture.expected {
+            failures.push(format!("{name}: expected {:?}, got {:?}", fixture.expected, actual));
+        }
+    }
+    assert!(failures.is_empty(), "{} of {} fixtures failed:\n{}", failures.len(), paths.len(), failures.join("\n"));
+}
@@ -0,0 +1,28 @@
+use std::{env, fs, process::Command};
+
+/// A failed guess must still remove the scratch directory the probe
+/// compiles in, with or without `--json`.
+#[test]
+fn failed_guess_cleans_up() {
+    let tmpdir = env::temp_dir().join(format!("config-guess-temp-dir-{}", std::process::id()));
+    fs::create_dir_all(&tmpdir).expect("temporary directory should be creatable");
+
+    for extra in [&[][..], &["--json"][..]] {
+        let status = Command::new(env!("CARGO_BIN_EXE_config-guess"))
+            .args(["--uname-machine", "mipsel", "--uname-system", "Linux"])
+            .args(extra)
+            .env("TMPDIR", &tmpdir)
+            .output()
+            .expect("config-guess should run")
+            .status;
+        assert!(!status.success(), "{extra:?}: the guess should fail");
+
+        let left = fs::read_dir(&tmpdir)
+            .expect("temporary directory should be readable")
+            .map(|entry| entry.expect("temporary directory should be readable").file_name())
+            .collect::<Vec<_>>();
+        assert!(left.is_empty(), "{extra:?}: left behind {left:?}");
+    }
+
+    fs::remove_dir(&tmpdir).expect("temporary directory should be empty");
+}
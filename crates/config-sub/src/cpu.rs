@@ -0,0 +1,255 @@
+component! {
+    /// The CPU type of a configuration name.
+    ///
+    /// These are the canonical CPU types config.sub accepts with any company
+    /// name, plus those that restrict or rewrite the company they are paired
+    /// with. Families that config.sub matches with a wildcard keep the part
+    /// that varies as their payload, e.g. `armv7l` is
+    /// `Cpu::Armv { version: "7l".into() }`.
+    pub enum Cpu {
+        Mil1750a = "1750a",
+        Amdahl580 = "580",
+        A29k = "a29k",
+        Aarch64 = "aarch64",
+        Aarch64Be = "aarch64_be",
+        Aarch64c = "aarch64c",
+        Abacus = "abacus",
+        Am33_2_0 = "am33_2.0",
+        Amdgcn = "amdgcn",
+        Arc = "arc",
+        Arc32 = "arc32",
+        Arc64 = "arc64",
+        Arceb = "arceb",
+        Arm = "arm",
+        Arm64e = "arm64e",
+        Arm64ec = "arm64ec",
+        Armle = "armle",
+        Armbe = "armbe",
+        Armel = "armel",
+        Armeb = "armeb",
+        Asmjs = "asmjs",
+        Avr = "avr",
+        Avr32 = "avr32",
+        Ba = "ba",
+        Be32 = "be32",
+        Be64 = "be64",
+        Bfin = "bfin",
+        Bpf = "bpf",
+        Bs2000 = "bs2000",
+        C30 = "c30",
+        C4x = "c4x",
+        C8051 = "c8051",
+        C90 = "c90",
+        Clipper = "clipper",
+        Cr16 = "cr16",
+        Craynv = "craynv",
+        Cris = "cris",
+        Crisv32 = "crisv32",
+        Crx = "crx",
+        Csky = "csky",
+        Cydra = "cydra",
+        D10v = "d10v",
+        D30v = "d30v",
+        Dlx = "dlx",
+        Dsp16xx = "dsp16xx",
+        E2k = "e2k",
+        Elxsi = "elxsi",
+        Epiphany = "epiphany",
+        F300 = "f300",
+        F301 = "f301",
+        F700 = "f700",
+        Fido = "fido",
+        Fr30 = "fr30",
+        Frv = "frv",
+        Ft32 = "ft32",
+        Fx80 = "fx80",
+        H8300 = "h8300",
+        H8500 = "h8500",
+        Hexagon = "hexagon",
+        I370 = "i370",
+        I860 = "i860",
+        I960 = "i960",
+        Ia16 = "ia16",
+        Ia64 = "ia64",
+        Intelgt = "intelgt",
+        Ip2k = "ip2k",
+        Iq2000 = "iq2000",
+        J90 = "j90",
+        Javascript = "javascript",
+        K1om = "k1om",
+        Kvx = "kvx",
+        Le32 = "le32",
+        Le64 = "le64",
+        Lm32 = "lm32",
+        Loongarch32 = "loongarch32",
+        Loongarch64 = "loongarch64",
+        M32c = "m32c",
+        M32r = "m32r",
+        M32rle = "m32rle",
+        M5200 = "m5200",
+        M68000 = "m68000",
+        M68010 = "m68010",
+        M68020 = "m68020",
+        M68030 = "m68030",
+        M68040 = "m68040",
+        M68060 = "m68060",
+        M6811 = "m6811",
+        M6812 = "m6812",
+        M68360 = "m68360",
+        M68hc11 = "m68hc11",
+        M68hc12 = "m68hc12",
+        M68hcs12x = "m68hcs12x",
+        M68k = "m68k",
+        M88110 = "m88110",
+        M88k = "m88k",
+        Maxq = "maxq",
+        Mb = "mb",
+        Mcore = "mcore",
+        Mep = "mep",
+        Metag = "metag",
+        Microblaze = "microblaze",
+        Microblazeel = "microblazeel",
+        Mmix = "mmix",
+        Mn10200 = "mn10200",
+        Mn10300 = "mn10300",
+        Moxie = "moxie",
+        Msp430 = "msp430",
+        Mt = "mt",
+        Nds32 = "nds32",
+        Nds32be = "nds32be",
+        Nds32le = "nds32le",
+        Neo = "neo",
+        Nfp = "nfp",
+        Nios = "nios",
+        Nios2 = "nios2",
+        Nios2eb = "nios2eb",
+        Nios2el = "nios2el",
+        None = "none",
+        Np1 = "np1",
+        Ns16k = "ns16k",
+        Ns32k = "ns32k",
+        Nse = "nse",
+        Nsr = "nsr",
+        Nsv = "nsv",
+        Nsx = "nsx",
+        Nvptx = "nvptx",
+        Open8 = "open8",
+        Or32 = "or32",
+        Orion = "orion",
+        Pdp10 = "pdp10",
+        Pdp11 = "pdp11",
+        Picochip = "picochip",
+        Pj = "pj",
+        Pjl = "pjl",
+        Pn = "pn",
+        Power = "power",
+        Powerpc = "powerpc",
+        Powerpc64 = "powerpc64",
+        Powerpc64le = "powerpc64le",
+        Powerpcle = "powerpcle",
+        Powerpcspe = "powerpcspe",
+        Pru = "pru",
+        Pyramid = "pyramid",
+        Riscv = "riscv",
+        Riscv32 = "riscv32",
+        Riscv32be = "riscv32be",
+        Riscv64 = "riscv64",
+        Riscv64be = "riscv64be",
+        Rl78 = "rl78",
+        Romp = "romp",
+        Rs6000 = "rs6000",
+        Rx = "rx",
+        S390 = "s390",
+        S390x = "s390x",
+        Score = "score",
+        Sparc = "sparc",
+        Sparc64 = "sparc64",
+        Sparc64b = "sparc64b",
+        Sparc64v = "sparc64v",
+        Sparc86x = "sparc86x",
+        Sparclet = "sparclet",
+        Sparclite = "sparclite",
+        Sparcv8 = "sparcv8",
+        Sparcv9 = "sparcv9",
+        Sparcv9b = "sparcv9b",
+        Sparcv9v = "sparcv9v",
+        Spu = "spu",
+        Sv1 = "sv1",
+        T90 = "t90",
+        Tahoe = "tahoe",
+        Tic30 = "tic30",
+        Tic4x = "tic4x",
+        Tic54x = "tic54x",
+        Tic55x = "tic55x",
+        Tic6x = "tic6x",
+        Tic80 = "tic80",
+        Tron = "tron",
+        Ubicom32 = "ubicom32",
+        V70 = "v70",
+        V810 = "v810",
+        V850 = "v850",
+        V850e = "v850e",
+        V850e1 = "v850e1",
+        V850e2 = "v850e2",
+        V850e2v3 = "v850e2v3",
+        V850es = "v850es",
+        Vax = "vax",
+        Vc4 = "vc4",
+        Visium = "visium",
+        W65 = "w65",
+        Wasm32 = "wasm32",
+        Wasm64 = "wasm64",
+        We32k = "we32k",
+        X86 = "x86",
+        X86_64 = "x86_64",
+        Xc16x = "xc16x",
+        Xgate = "xgate",
+        Xps100 = "xps100",
+        Xstormy16 = "xstormy16",
+        Ymp = "ymp",
+        Z80 = "z80",
+        Z8k = "z8k",
+        ;
+        Alpha { variant } = "alpha" * if [
+            "alpha",
+            "alpha64",
+            "alpha64ev56",
+            "alpha64ev6[78]",
+            "alpha64ev[4-8]",
+            "alpha64pca5[67]",
+            "alphaev56",
+            "alphaev6[78]",
+            "alphaev[4-8]",
+            "alphapca5[67]",
+        ],
+        Armv { version } = "armv" *,
+        /// The Convex C1, C2 and C3 series.
+        Convex { model } = "c" * if ["c[123]*"],
+        Hppa { variant } = "hppa" * if ["hppa", "hppa1.[01]", "hppa2.0", "hppa2.0[nw]", "hppa64"],
+        /// `i386`, `i486`, `i586`, `i686`, ...
+        I86 { generation } = "i" * "86",
+        M683x2 { model } = "m683" * "2" if ["m683?2"],
+        Mips { variant } = "mips" *,
+        Nanomips { variant } = "nanomips" *,
+        Or1k { variant } = "or1k" *,
+        Sh { variant } = "sh" * if [
+            "sh",
+            "sh64",
+            "sh64le",
+            "sh[12345][lb]e",
+            "sh[1234]",
+            "sh[1234]e[lb]",
+            "sh[23]e",
+            "sh[23]ele",
+            "sh[24]a",
+            "sh[24]ae[lb]",
+            "sh[lb]e",
+            "she[lb]",
+            "shl",
+        ],
+        Sx { model } = "sx" *,
+        Thumbv7 { variant } = "thumbv7" *,
+        Tile { variant } = "tile" *,
+        Xtensa { variant } = "xtensa" *,
+    }
+}
@@ -0,0 +1,26 @@
+component! {
+    /// The kernel of a four-part configuration name.
+    pub enum Kernel {
+        Ironclad = "ironclad",
+        Linux = "linux",
+        Managarm = "managarm",
+        /// No kernel at all, i.e. freestanding or bare metal.
+        None = "none",
+        Nto = "nto",
+        Os2 = "os2",
+        Rtmk = "rtmk",
+        Storm = "storm",
+        Uclinux = "uclinux",
+        VxWorks = "vxworks",
+        ;
+        CloudAbi { version } = "cloudabi" *,
+        Ios { version } = "ios" *,
+        KFreeBsd { version } = "kfreebsd" *,
+        KNetBsd { version } = "knetbsd" *,
+        KOpenSolaris { version } = "kopensolaris" *,
+        NetBsd { version } = "netbsd" *,
+        TvOs { version } = "tvos" *,
+        WatchOs { version } = "watchos" *,
+        Windows { version } = "windows" *,
+    }
+}
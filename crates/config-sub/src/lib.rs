@@ -1,8 +1,114 @@
-use glob::Pattern;
+use std::fmt;
+
+macro_rules! matches_glob {
+    ($input:expr, $pattern:expr) => {
+        ::glob::Pattern::new($pattern).expect("pattern should be valid").matches($input)
+    };
+}
+
+/// Define a configuration name component enum.
+///
+/// Unit variants match their name exactly. Variants with a field match a
+/// prefix (and an optional suffix), keep whatever is in between as their
+/// payload, and may be restricted further by a list of globs. When several
+/// variants match, the one with the longest prefix wins. Everything else
+/// becomes `Other`.
+macro_rules! component {
+    (@suffix) => { "" };
+    (@suffix $suffix:literal) => { $suffix };
+    (@guard $s:ident) => { true };
+    (@guard $s:ident [$($glob:literal),*]) => { [$($glob),*].iter().any(|glob| matches_glob!($s, glob)) };
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $( $(#[$unit_meta:meta])* $unit:ident = $unit_name:literal, )*
+            ;
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident { $field:ident } = $prefix:literal * $($suffix:literal)? $(if [$($glob:literal),* $(,)?])?,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $( $(#[$unit_meta])* $unit, )*
+            $( $(#[$variant_meta])* $variant { $field: String }, )*
+            /// A name that is not otherwise recognized, such as a `local` one.
+            Other(String),
+        }
+
+        impl $name {
+            /// Whether this is one of the names config.sub recognizes.
+            pub fn is_known(&self) -> bool {
+                !matches!(self, Self::Other(_))
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(s: &str) -> Self {
+                $(
+                    if s == $unit_name {
+                        return Self::$unit;
+                    }
+                )*
+                let best: Option<(usize, Self)> = None;
+                $(
+                    let suffix = component!(@suffix $($suffix)?);
+                    let best = if s.len() >= $prefix.len() + suffix.len()
+                        && s.starts_with($prefix)
+                        && s.ends_with(suffix)
+                        && component!(@guard s $([$($glob),*])?)
+                        && best.as_ref().is_none_or(|(len, _)| $prefix.len() > *len)
+                    {
+                        let $field = s[$prefix.len()..s.len() - suffix.len()].to_owned();
+                        Some(($prefix.len(), Self::$variant { $field }))
+                    } else {
+                        best
+                    };
+                )*
+                best.map(|(_, component)| component).unwrap_or_else(|| Self::Other(s.into()))
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = ::std::convert::Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(Self::from(s))
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match self {
+                    $( Self::$unit => f.write_str($unit_name), )*
+                    $( Self::$variant { $field } => write!(f, "{}{}{}", $prefix, $field, component!(@suffix $($suffix)?)), )*
+                    Self::Other(s) => f.write_str(s),
+                }
+            }
+        }
+    };
+}
+
+mod cpu;
+mod kernel;
+mod obj;
+mod os;
+mod triple;
+mod vendor;
+
+pub use cpu::Cpu;
+pub use kernel::Kernel;
+pub use obj::Obj;
+pub use os::Os;
+pub use triple::Triple;
+pub use vendor::Vendor;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Error {
     MoreThanFourComponents,
+    MachineNotRecognized(String),
     BlankOsOnlyAllowedWithExplicitMachineCodeFileFormat,
     OsNotRecognized(String),
     MachineCodeFormatNotRecognized(String),
@@ -14,284 +120,973 @@ pub enum Error {
     KernelNotKnownToWorkWithOs(String, String),
 }
 
-macro_rules! matches_glob {
-    ($input:expr, $pattern:expr) => {
-        Pattern::new($pattern).expect("pattern should be valid").matches($input)
-    };
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MoreThanFourComponents => write!(f, "more than four components"),
+            Error::MachineNotRecognized(machine) => write!(f, "machine '{}' not recognized", machine),
+            Error::BlankOsOnlyAllowedWithExplicitMachineCodeFileFormat => {
+                write!(f, "Blank OS only allowed with explicit machine code file format")
+            }
+            Error::OsNotRecognized(os) => write!(f, "OS '{}' not recognized", os),
+            Error::MachineCodeFormatNotRecognized(obj) => write!(f, "Machine code format '{}' not recognized", obj),
+            Error::CpuIsNotValidWithOs(cpu, os) => write!(f, "cpu '{}' is not valid with os '{}'", cpu, os),
+            Error::LibcNeedsExplicitKernel(os) => write!(f, "libc '{}' needs explicit kernel", os),
+            Error::OsNeedsExplicitKernel(os) => write!(f, "'{}' needs explicit kernel", os),
+            Error::KernelDoesNotSupportOs(kernel, os) => write!(f, "'{}' does not support '{}'", kernel, os),
+            Error::OsNeedsWindows(os) => write!(f, "'{}' needs 'windows'", os),
+            Error::KernelNotKnownToWorkWithOs(kernel, os) => {
+                write!(f, "Kernel '{}' not known to work with OS '{}'", kernel, os)
+            }
+        }
+    }
 }
 
+impl std::error::Error for Error {}
+
+/// Canonicalize a configuration name, returning it in its string form.
+///
+/// This is a thin wrapper over parsing a [`Triple`].
 pub fn config_sub(input: impl AsRef<str>) -> Result<String, Error> {
     fn inner(input: &str) -> Result<String, Error> {
-        if input.contains("local") {
-            // TODO: Verify format (no spaces, alphanumeric, etc.)
-            return Ok(input.into());
-        }
-
-        let fields = input.split('-').collect::<Vec<_>>();
-        
-        let (basic_machine, basic_os): (String, String) = match fields.len() {
-            _ => return Err(Error::MoreThanFourComponents),
-            4 => (format!("{}-{}", fields[0], fields[1]), format!("{}-{}", fields[2], fields[3])),
-            3 => match (fields[1], fields[2]) {
-                (a, b) if 
-                    (a.starts_with("cloudabi") && b.starts_with("eabi"))
-                    || (a.starts_with("kfreebsd") && b.starts_with("gnu"))
-                    || (a.starts_with("knetbsd") && b.starts_with("gnu"))
-                    || (a.starts_with("kopensolaris") && b.starts_with("gnu"))
-                    || a == "ironclad"
-                    || a == "linux"
-                    || a == "managarm"
-                    || (a.starts_with("netbsd") && b.starts_with("eabi"))
-                    || (a.starts_with("netbsd") && b.starts_with("gnu"))
-                    || (a == "nto" && b.starts_with("qnx"))
-                    || (a == "os2" && b.starts_with("emx"))
-                    || (a == "rtmk" && b.starts_with("nova"))
-                    || (a == "storm" && b.starts_with("chaos"))
-                    || (a == "uclinux" && b.starts_with("gnu"))
-                    || (a == "uclinux" && b.starts_with("uclibc"))
-                    || (a == "windows")
-                    => (fields[0].into(), format!("{}-{}", fields[1], fields[2])),
-                ("android", "linux") => (format!("{}-unknown", fields[0]), "linux-android".into()),
-                _ => (format!("{}-{}", fields[0], fields[1]), fields[2].into()),
-            },
-            2 => match (fields[0], fields[1]) {
-                ("convex", b) if matches_glob!(b, "c[12]") || matches_glob!(b, "c3[248]") => (format!("{}-convex", fields[1]), "".into()),
-                ("decstation", "3100") => ("mips-dec".into(), "".into()),
-                _ => match fields[1] {
-                    s if matches_glob!(s, "sun*os*") => (fields[0].into(), fields[1].into()),
-                    s if
-                        s.starts_with("3100")
-                        || s.starts_with("32")
-                        || s.starts_with("3300")
-                        || s.starts_with("3600")
-                        || s.starts_with("7300")
-                        || s == "acorn"
-                        || s.starts_with("altos")
-                        || s == "apollo"
-                        || s == "apple"
-                        || s == "atari"
-                        || s.starts_with("att")
-                        || s == "axis"
-                        || s == "be"
-                        || s == "bull"
-                        || s == "cbm"
-                        || s == "ccur"
-                        || s == "cisco"
-                        || s == "commodore"
-                        || s.starts_with("convergent")
-                        || s.starts_with("convex")
-                        || s == "cray"
-                        || s == "crds"
-                        || s.starts_with("dec")
-                        || s.starts_with("delta")
-                        || s == "dg"
-                        || s == "digital"
-                        || s == "dolphin"
-                        || s.starts_with("encore")
-                        || s == "gould"
-                        || s == "harris"
-                        || s == "highlevel"
-                        || s.starts_with("hitachi")
-                        || s == "hp"
-                        || s.starts_with("ibm")
-                        || s == "intergraph"
-                        || s.starts_with("isi")
-                        || s == "knuth"
-                        || s == "masscomp"
-                        || s.starts_with("microblaze")
-                        || s.starts_with("mips")
-                        || s.starts_with("motorola")
-                        || s.starts_with("ncr")
-                        || s == "news"
-                        || s == "next"
-                        || s == "ns"
-                        || s == "oki"
-                        || s.starts_with("omron")
-                        || s.starts_with("pc533")
-                        || s == "rebel"
-                        || s == "rom68k"
-                        || s == "rombug"
-                        || s == "semi"
-                        || s.starts_with("sequent")
-                        || s.starts_with("sgi")
-                        || s == "siemens"
-                        || s == "sim"
-                        || s == "sni"
-                        || s.starts_with("sony")
-                        || s == "stratus"
-                        || s == "sun"
-                        || matches_glob!(s, "sun[234]*")
-                        || s == "tektronix"
-                        || s.starts_with("tti")
-                        || s == "ultra"
-                        || s.starts_with("unicom")
-                        || s == "wec"
-                        || s == "winbond"
-                        || s == "wrs"
-                        => (format!("{}-{}", fields[0], fields[1]), "".into()),
-                    s if s.starts_with("tock") || s.starts_with("zephyr") => (format!("{}-unknown", fields[0]), fields[1].into()),
-                    _ => (fields[0].into(), fields[1].into()),
-                }
+        Ok(input.parse::<Triple>()?.to_string())
+    }
+    inner(input.as_ref())
+}
+
+/// `${basic_os:-default}`
+fn default_os(basic_os: &mut String, default: &str) {
+    if basic_os.is_empty() {
+        *basic_os = default.into();
+    }
+}
+
+/// `echo "$1" | sed -e 's/86.*/86/'`
+fn up_to_86(input: &str) -> &str {
+    input.find("86").map(|i| &input[..i + 2]).unwrap_or(input)
+}
+
+pub(crate) fn canonicalize(input: &str) -> Result<Triple, Error> {
+    let fields = input.split('-').collect::<Vec<_>>();
+
+    let (basic_machine, mut basic_os): (String, String) = match fields.len() {
+        4 => (format!("{}-{}", fields[0], fields[1]), format!("{}-{}", fields[2], fields[3])),
+        3 => match (fields[1], fields[2]) {
+            (a, b) if 
+                (a.starts_with("cloudabi") && b.starts_with("eabi"))
+                || (a.starts_with("kfreebsd") && b.starts_with("gnu"))
+                || (a.starts_with("knetbsd") && b.starts_with("gnu"))
+                || (a.starts_with("kopensolaris") && b.starts_with("gnu"))
+                || a == "ironclad"
+                || a == "linux"
+                || a == "managarm"
+                || (a.starts_with("netbsd") && b.starts_with("eabi"))
+                || (a.starts_with("netbsd") && b.starts_with("gnu"))
+                || (a == "nto" && b.starts_with("qnx"))
+                || (a == "os2" && b.starts_with("emx"))
+                || (a == "rtmk" && b.starts_with("nova"))
+                || (a == "storm" && b.starts_with("chaos"))
+                || (a == "uclinux" && b.starts_with("gnu"))
+                || (a == "uclinux" && b.starts_with("uclibc"))
+                || (a == "windows")
+                => (fields[0].into(), format!("{}-{}", fields[1], fields[2])),
+            ("android", "linux") => (format!("{}-unknown", fields[0]), "linux-android".into()),
+            _ => (format!("{}-{}", fields[0], fields[1]), fields[2].into()),
+        },
+        2 => match (fields[0], fields[1]) {
+            ("convex", b) if matches_glob!(b, "c[12]") || matches_glob!(b, "c3[248]") => (format!("{}-convex", fields[1]), "".into()),
+            ("decstation", "3100") => ("mips-dec".into(), "".into()),
+            _ => match fields[1] {
+                s if matches_glob!(s, "sun*os*") => (fields[0].into(), fields[1].into()),
+                s if
+                    s.starts_with("3100")
+                    || s.starts_with("32")
+                    || s.starts_with("3300")
+                    || s.starts_with("3600")
+                    || s.starts_with("7300")
+                    || s == "acorn"
+                    || s.starts_with("altos")
+                    || s == "apollo"
+                    || s == "apple"
+                    || s == "atari"
+                    || s.starts_with("att")
+                    || s == "axis"
+                    || s == "be"
+                    || s == "bull"
+                    || s == "cbm"
+                    || s == "ccur"
+                    || s == "cisco"
+                    || s == "commodore"
+                    || s.starts_with("convergent")
+                    || s.starts_with("convex")
+                    || s == "cray"
+                    || s == "crds"
+                    || s.starts_with("dec")
+                    || s.starts_with("delta")
+                    || s == "dg"
+                    || s == "digital"
+                    || s == "dolphin"
+                    || s.starts_with("encore")
+                    || s == "gould"
+                    || s == "harris"
+                    || s == "highlevel"
+                    || s.starts_with("hitachi")
+                    || s == "hp"
+                    || s.starts_with("ibm")
+                    || s == "intergraph"
+                    || s.starts_with("isi")
+                    || s == "knuth"
+                    || s == "masscomp"
+                    || s.starts_with("microblaze")
+                    || s.starts_with("mips")
+                    || s.starts_with("motorola")
+                    || s.starts_with("ncr")
+                    || s == "news"
+                    || s == "next"
+                    || s == "ns"
+                    || s == "oki"
+                    || s.starts_with("omron")
+                    || s.starts_with("pc533")
+                    || s == "rebel"
+                    || s == "rom68k"
+                    || s == "rombug"
+                    || s == "semi"
+                    || s.starts_with("sequent")
+                    || s.starts_with("sgi")
+                    || s == "siemens"
+                    || s == "sim"
+                    || s == "sni"
+                    || s.starts_with("sony")
+                    || s == "stratus"
+                    || s == "sun"
+                    || matches_glob!(s, "sun[234]*")
+                    || s == "tektronix"
+                    || s.starts_with("tti")
+                    || s == "ultra"
+                    || s.starts_with("unicom")
+                    || s == "wec"
+                    || s == "winbond"
+                    || s == "wrs"
+                    => (format!("{}-{}", fields[0], fields[1]), "".into()),
+                s if s.starts_with("tock") || s.starts_with("zephyr") => (format!("{}-unknown", fields[0]), fields[1].into()),
+                _ => (fields[0].into(), fields[1].into()),
             }
-            1 => match fields[0] {
-                "386bsd" => ("i386-pc".into(), "bsd".into()),
-                _ => todo!(),
-            },
-            0 => panic!("input should have at least one field"),
-        };
+        }
+        // Convert single-component short-hands not valid as part of
+        // multi-component configurations.
+        1 => match fields[0] {
+            "386bsd" => ("i386-pc".into(), "bsd".into()),
+            "a29khif" => ("a29k-amd".into(), "udi".into()),
+            "adobe68k" => ("m68010-adobe".into(), "scout".into()),
+            "alliant" => ("fx80-alliant".into(), "".into()),
+            "altos" | "altos3068" => ("m68k-altos".into(), "".into()),
+            "am29k" => ("a29k-none".into(), "bsd".into()),
+            "amdahl" => ("580-amdahl".into(), "sysv".into()),
+            "amiga" => ("m68k-unknown".into(), "".into()),
+            "amigaos" | "amigados" => ("m68k-unknown".into(), "amigaos".into()),
+            "amigaunix" | "amix" => ("m68k-unknown".into(), "sysv4".into()),
+            "apollo68" => ("m68k-apollo".into(), "sysv".into()),
+            "apollo68bsd" => ("m68k-apollo".into(), "bsd".into()),
+            "aros" => ("i386-pc".into(), "aros".into()),
+            "aux" => ("m68k-apple".into(), "aux".into()),
+            "balance" => ("ns32k-sequent".into(), "dynix".into()),
+            "blackfin" => ("bfin-unknown".into(), "linux".into()),
+            "cegcc" => ("arm-unknown".into(), "cegcc".into()),
+            "cray" => ("j90-cray".into(), "unicos".into()),
+            "crds" | "unos" => ("m68k-crds".into(), "".into()),
+            "da30" => ("m68k-da30".into(), "".into()),
+            "decstation" | "pmax" | "pmin" | "dec3100" | "decstatn" => ("mips-dec".into(), "".into()),
+            "delta88" => ("m88k-motorola".into(), "sysv3".into()),
+            "dicos" => ("i686-pc".into(), "dicos".into()),
+            "djgpp" => ("i586-pc".into(), "msdosdjgpp".into()),
+            "ebmon29k" => ("a29k-amd".into(), "ebmon".into()),
+            "es1800" | "OSE68k" | "ose68k" | "ose" | "OSE" => ("m68k-ericsson".into(), "ose".into()),
+            "gmicro" => ("tron-gmicro".into(), "sysv".into()),
+            "go32" => ("i386-pc".into(), "go32".into()),
+            "h8300hms" => ("h8300-hitachi".into(), "hms".into()),
+            "h8300xray" => ("h8300-hitachi".into(), "xray".into()),
+            "h8500hms" => ("h8500-hitachi".into(), "hms".into()),
+            "harris" => ("m88k-harris".into(), "sysv3".into()),
+            "hp300" | "hp300hpux" => ("m68k-hp".into(), "hpux".into()),
+            "hp300bsd" => ("m68k-hp".into(), "bsd".into()),
+            "hppaosf" => ("hppa1.1-hp".into(), "osf".into()),
+            "hppro" => ("hppa1.1-hp".into(), "proelf".into()),
+            "i386mach" => ("i386-mach".into(), "mach".into()),
+            "isi68" | "isi" => ("m68k-isi".into(), "sysv".into()),
+            "m68knommu" => ("m68k-unknown".into(), "linux".into()),
+            "magnum" | "m3230" => ("mips-mips".into(), "sysv".into()),
+            "merlin" => ("ns32k-utek".into(), "sysv".into()),
+            "mingw64" => ("x86_64-pc".into(), "mingw64".into()),
+            "mingw32" => ("i686-pc".into(), "mingw32".into()),
+            "mingw32ce" => ("arm-unknown".into(), "mingw32ce".into()),
+            "monitor" => ("m68k-rom68k".into(), "coff".into()),
+            "morphos" => ("powerpc-unknown".into(), "morphos".into()),
+            "moxiebox" => ("moxie-unknown".into(), "moxiebox".into()),
+            "msdos" => ("i386-pc".into(), "msdos".into()),
+            "msys" => ("i686-pc".into(), "msys".into()),
+            "mvs" => ("i370-ibm".into(), "mvs".into()),
+            "nacl" => ("le32-unknown".into(), "nacl".into()),
+            "ncr3000" => ("i486-ncr".into(), "sysv4".into()),
+            "netbsd386" => ("i386-pc".into(), "netbsd".into()),
+            "netwinder" => ("armv4l-rebel".into(), "linux".into()),
+            "news" | "news700" | "news800" | "news900" => ("m68k-sony".into(), "newsos".into()),
+            "news1000" => ("m68030-sony".into(), "newsos".into()),
+            "necv70" => ("v70-nec".into(), "sysv".into()),
+            "nh3000" => ("m68k-harris".into(), "cxux".into()),
+            "nh4000" | "nh5000" => ("m88k-harris".into(), "cxux".into()),
+            "nindy960" => ("i960-intel".into(), "nindy".into()),
+            "mon960" => ("i960-intel".into(), "mon960".into()),
+            "nonstopux" => ("mips-compaq".into(), "nonstopux".into()),
+            "os400" => ("powerpc-ibm".into(), "os400".into()),
+            "OSE68000" | "ose68000" => ("m68000-ericsson".into(), "ose".into()),
+            "os68k" => ("m68k-none".into(), "os68k".into()),
+            "paragon" => ("i860-intel".into(), "osf".into()),
+            "parisc" => ("hppa-unknown".into(), "linux".into()),
+            "psp" => ("mipsallegrexel-sony".into(), "psp".into()),
+            "pw32" => ("i586-unknown".into(), "pw32".into()),
+            "rdos" | "rdos64" => ("x86_64-pc".into(), "rdos".into()),
+            "rdos32" => ("i386-pc".into(), "rdos".into()),
+            "rom68k" => ("m68k-rom68k".into(), "coff".into()),
+            "sa29200" => ("a29k-amd".into(), "udi".into()),
+            "sei" => ("mips-sei".into(), "seiux".into()),
+            "sequent" => ("i386-sequent".into(), "".into()),
+            "sps7" => ("m68k-bull".into(), "sysv2".into()),
+            "st2000" => ("m68k-tandem".into(), "".into()),
+            "stratus" => ("i860-stratus".into(), "sysv4".into()),
+            "sun2" => ("m68000-sun".into(), "".into()),
+            "sun2os3" => ("m68000-sun".into(), "sunos3".into()),
+            "sun2os4" => ("m68000-sun".into(), "sunos4".into()),
+            "sun3" => ("m68k-sun".into(), "".into()),
+            "sun3os3" => ("m68k-sun".into(), "sunos3".into()),
+            "sun3os4" => ("m68k-sun".into(), "sunos4".into()),
+            "sun4" => ("sparc-sun".into(), "".into()),
+            "sun4os3" => ("sparc-sun".into(), "sunos3".into()),
+            "sun4os4" => ("sparc-sun".into(), "sunos4".into()),
+            "sun4sol2" => ("sparc-sun".into(), "solaris2".into()),
+            "sun386" | "sun386i" | "roadrunner" => ("i386-sun".into(), "".into()),
+            "sv1" => ("sv1-cray".into(), "unicos".into()),
+            "symmetry" => ("i386-sequent".into(), "dynix".into()),
+            "t3e" => ("alphaev5-cray".into(), "unicos".into()),
+            "t90" => ("t90-cray".into(), "unicos".into()),
+            "toad1" => ("pdp10-xkl".into(), "tops20".into()),
+            "tpf" => ("s390x-ibm".into(), "tpf".into()),
+            "udi29k" => ("a29k-amd".into(), "udi".into()),
+            "ultra3" => ("a29k-nyu".into(), "sym1".into()),
+            "v810" | "necv810" => ("v810-nec".into(), "none".into()),
+            "vaxv" => ("vax-dec".into(), "sysv".into()),
+            "vms" => ("vax-dec".into(), "vms".into()),
+            "vsta" => ("i386-pc".into(), "vsta".into()),
+            "vxworks960" => ("i960-wrs".into(), "vxworks".into()),
+            "vxworks68" => ("m68k-wrs".into(), "vxworks".into()),
+            "vxworks29k" => ("a29k-wrs".into(), "vxworks".into()),
+            "xbox" => ("i686-pc".into(), "mingw32".into()),
+            "ymp" => ("ymp-cray".into(), "unicos".into()),
+            _ => (fields[0].into(), "".into()),
+        },
+        _ => return Err(Error::MoreThanFourComponents),
+    };
 
-        let (cpu, mut vendor): (String, String) = match basic_machine.as_str() {
-            "w89k" => ("hppa1.1".into(), "winbond".into()),
-            _ => todo!(),
-            s if matches_glob!(s, "i*86") || s == "x86_64" => (s.into(), "pc".into()),
-            "pc98" => ("i386".into(), "pc".into()),
-            "x64" | "amd64" => ("x86_64".into(), "pc".into()),
-            s => s.split_once('-').map(|(a, b)| (a.into(), b.into())).unwrap_or_else(|| (s.into(), "unknown".into())),
-        };
+    // Decode 1-component or ad-hoc basic machines
+    let (mut cpu, mut vendor): (String, String) = match basic_machine.as_str() {
+        // Here we handle the default manufacturer of certain CPU types. It is in
+        // some cases the only manufacturer, in others, it is the most popular.
+        "w89k" => ("hppa1.1".into(), "winbond".into()),
+        "op50n" => ("hppa1.1".into(), "oki".into()),
+        "op60c" => ("hppa1.1".into(), "oki".into()),
+        s if s.starts_with("ibm") => ("i370".into(), "ibm".into()),
+        "orion105" => ("clipper".into(), "highlevel".into()),
+        "mac" | "mpw" | "mac-mpw" => ("m68k".into(), "apple".into()),
+        "pmac" | "pmac-mpw" => ("powerpc".into(), "apple".into()),
 
-        drop(basic_machine);
+        // Recognize the various machine names and aliases which stand
+        // for a CPU type and a company and sometimes even an OS.
+        "3b1" | "7300" | "7300-att" | "att-7300" | "pc7300" | "safari" | "unixpc" => ("m68000".into(), "att".into()),
+        s if s.starts_with("3b") => ("we32k".into(), "att".into()),
+        s if s.starts_with("bluegene") => {
+            basic_os = "cnk".into();
+            ("powerpc".into(), "ibm".into())
+        }
+        s if s.starts_with("decsystem10") || s.starts_with("dec10") => {
+            basic_os = "tops10".into();
+            ("pdp10".into(), "dec".into())
+        }
+        s if s.starts_with("decsystem20") || s.starts_with("dec20") => {
+            basic_os = "tops20".into();
+            ("pdp10".into(), "dec".into())
+        }
+        "delta" | "3300" | "delta-motorola" | "3300-motorola" | "motorola-delta" | "motorola-3300" => {
+            ("m68k".into(), "motorola".into())
+        }
+        // This used to be dpx2*, but that gets the RS6000-based
+        // DPX/20 and the x86-based DPX/2-100 wrong. See
+        // https://oldskool.silicium.org/stations/bull_dpx20.htm
+        // https://www.feb-patrimoine.com/english/bull_dpx2.htm
+        // https://www.feb-patrimoine.com/english/unix_and_bull.htm
+        s if s == "dpx2" || matches_glob!(s, "dpx2[23]00") || matches_glob!(s, "dpx2[23]xx") => {
+            ("m68k".into(), "bull".into())
+        }
+        "dpx2100" | "dpx21xx" => ("i386".into(), "bull".into()),
+        "dpx20" => ("rs6000".into(), "bull".into()),
+        "encore" | "umax" | "mmax" => ("ns32k".into(), "encore".into()),
+        "elxsi" => {
+            default_os(&mut basic_os, "bsd");
+            ("elxsi".into(), "elxsi".into())
+        }
+        "fx2800" => ("i860".into(), "alliant".into()),
+        "genix" => ("ns32k".into(), "ns".into()),
+        s if s.starts_with("h3050r") || s.starts_with("hiux") => {
+            basic_os = "hiuxwe2".into();
+            ("hppa1.1".into(), "hitachi".into())
+        }
+        s if matches_glob!(s, "hp3k9[0-9][0-9]") || matches_glob!(s, "hp9[0-9][0-9]") => ("hppa1.0".into(), "hp".into()),
+        s if matches_glob!(s, "hp9k2[0-9][0-9]") || matches_glob!(s, "hp9k31[0-9]") => ("m68000".into(), "hp".into()),
+        s if matches_glob!(s, "hp9k3[2-9][0-9]") => ("m68k".into(), "hp".into()),
+        s if matches_glob!(s, "hp9k6[0-9][0-9]") || matches_glob!(s, "hp6[0-9][0-9]") => ("hppa1.0".into(), "hp".into()),
+        s if matches_glob!(s, "hp9k7[0-79][0-9]") || matches_glob!(s, "hp7[0-79][0-9]") => ("hppa1.1".into(), "hp".into()),
+        // FIXME: really hppa2.0-hp
+        s if matches_glob!(s, "hp9k78[0-9]") || matches_glob!(s, "hp78[0-9]") => ("hppa1.1".into(), "hp".into()),
+        // FIXME: really hppa2.0-hp
+        s if matches_glob!(s, "hp9k8[67]1")
+            || matches_glob!(s, "hp8[67]1")
+            || matches_glob!(s, "hp9k80[24]")
+            || matches_glob!(s, "hp80[24]")
+            || matches_glob!(s, "hp9k8[78]9")
+            || matches_glob!(s, "hp8[78]9")
+            || s == "hp9k893"
+            || s == "hp893" =>
+        {
+            ("hppa1.1".into(), "hp".into())
+        }
+        s if matches_glob!(s, "hp9k8[0-9][13679]") || matches_glob!(s, "hp8[0-9][13679]") => ("hppa1.1".into(), "hp".into()),
+        s if matches_glob!(s, "hp9k8[0-9][0-9]") || matches_glob!(s, "hp8[0-9][0-9]") => ("hppa1.0".into(), "hp".into()),
+        s if matches_glob!(s, "i*86v32") => {
+            basic_os = "sysv32".into();
+            (up_to_86(input).into(), "pc".into())
+        }
+        s if matches_glob!(s, "i*86v4*") => {
+            basic_os = "sysv4".into();
+            (up_to_86(input).into(), "pc".into())
+        }
+        s if matches_glob!(s, "i*86v") => {
+            basic_os = "sysv".into();
+            (up_to_86(input).into(), "pc".into())
+        }
+        s if matches_glob!(s, "i*86sol2") => {
+            basic_os = "solaris2".into();
+            (up_to_86(input).into(), "pc".into())
+        }
+        "j90" | "j90-cray" => {
+            default_os(&mut basic_os, "unicos");
+            ("j90".into(), "cray".into())
+        }
+        "iris" | "iris4d" => {
+            if !basic_os.starts_with("irix") {
+                basic_os = "irix4".into();
+            }
+            ("mips".into(), "sgi".into())
+        }
+        "miniframe" => ("m68000".into(), "convergent".into()),
+        s if s.ends_with("mint")
+            || matches_glob!(s, "mint[0-9]*")
+            || s.ends_with("MiNT")
+            || matches_glob!(s, "*MiNT[0-9]*") =>
+        {
+            basic_os = "mint".into();
+            ("m68k".into(), "atari".into())
+        }
+        "news-3600" | "risc-news" => {
+            basic_os = "newsos".into();
+            ("mips".into(), "sony".into())
+        }
+        s if s == "next" || matches_glob!(s, "m*-next") => ("m68k".into(), "next".into()),
+        "np1" => ("np1".into(), "gould".into()),
+        s if s.starts_with("op50n-") || s.starts_with("op60c-") => {
+            basic_os = "proelf".into();
+            ("hppa1.1".into(), "oki".into())
+        }
+        "pa-hitachi" => {
+            basic_os = "hiuxwe2".into();
+            ("hppa1.1".into(), "hitachi".into())
+        }
+        "pbd" => ("sparc".into(), "tti".into()),
+        "pbb" => ("m68k".into(), "tti".into()),
+        "pc532" => ("ns32k".into(), "pc532".into()),
+        "pn" => ("pn".into(), "gould".into()),
+        "power" => ("power".into(), "ibm".into()),
+        "ps2" => ("i386".into(), "ibm".into()),
+        "rm400" | "rm600" => ("mips".into(), "siemens".into()),
+        s if s == "rtpc" || s.starts_with("rtpc-") => ("romp".into(), "ibm".into()),
+        "sde" => {
+            default_os(&mut basic_os, "elf");
+            ("mipsisa32".into(), "sde".into())
+        }
+        "simso-wrs" => {
+            basic_os = "vxworks".into();
+            ("sparclite".into(), "wrs".into())
+        }
+        "tower" | "tower-32" => ("m68k".into(), "ncr".into()),
+        s if s.starts_with("vpp") || s == "vx" || s.starts_with("vx-") => ("f301".into(), "fujitsu".into()),
+        "w65" => ("w65".into(), "wdc".into()),
+        s if s.starts_with("w89k-") => {
+            basic_os = "proelf".into();
+            ("hppa1.1".into(), "winbond".into())
+        }
+        "none" => ("none".into(), "none".into()),
+        s if s == "leon" || matches_glob!(s, "leon[3-9]") => ("sparc".into(), s.into()),
+        s if s.starts_with("leon-") || matches_glob!(s, "leon[3-9]-*") => {
+            ("sparc".into(), s.split('-').next().unwrap_or_default().into())
+        }
+
+        s if s.contains('-') => s.split_once('-').map(|(a, b)| (a.into(), b.into())).unwrap_or_default(),
+        // We use 'pc' rather than 'unknown'
+        // because (1) that's what they normally are, and
+        // (2) the word "unknown" tends to confuse beginning users.
+        s if matches_glob!(s, "i*86") || s == "x86_64" => (s.into(), "pc".into()),
+        // These rules are duplicated from below for sake of the special case above;
+        // i.e. things that normalized to x86 arches should also default to "pc"
+        "pc98" => ("i386".into(), "pc".into()),
+        "x64" | "amd64" => ("x86_64".into(), "pc".into()),
+        // Recognize the basic CPU types without company name.
+        s => (s.into(), "unknown".into()),
+    };
+
+    drop(basic_machine);
+
+    // Decode basic machines in the full and proper CPU-Company form.
+    let machine = format!("{cpu}-{vendor}");
+    match machine.as_str() {
+        // Here we handle the default manufacturer of certain CPU types in canonical form.
+        // It is in some cases the only manufacturer, in others, it is the most popular.
+        s if matches_glob!(s, "c[12]-convex")
+            || matches_glob!(s, "c[12]-unknown")
+            || matches_glob!(s, "c3[248]-convex")
+            || matches_glob!(s, "c3[248]-unknown") =>
+        {
+            vendor = "convex".into();
+            default_os(&mut basic_os, "bsd");
+        }
+        "craynv-unknown" => {
+            vendor = "cray".into();
+            default_os(&mut basic_os, "unicosmp");
+        }
+        "c90-unknown" | "c90-cray" => {
+            vendor = "cray".into();
+            default_os(&mut basic_os, "unicos");
+        }
+        "fx80-unknown" => vendor = "alliant".into(),
+        "romp-unknown" => vendor = "ibm".into(),
+        "mmix-unknown" => vendor = "knuth".into(),
+        "microblaze-unknown" | "microblazeel-unknown" => vendor = "xilinx".into(),
+        "rs6000-unknown" => vendor = "ibm".into(),
+        "vax-unknown" => vendor = "dec".into(),
+        "pdp11-unknown" => vendor = "dec".into(),
+        "we32k-unknown" => vendor = "att".into(),
+        "cydra-unknown" => vendor = "cydrome".into(),
+        s if s.starts_with("i370-ibm") => vendor = "ibm".into(),
+        "orion-unknown" => vendor = "highlevel".into(),
+        "xps-unknown" | "xps100-unknown" => {
+            cpu = "xps100".into();
+            vendor = "honeywell".into();
+        }
+
+        // Here we normalize CPU types with a missing or matching vendor
+        "armh-unknown" | "armh-alt" => {
+            cpu = "armv7l".into();
+            vendor = "alt".into();
+            default_os(&mut basic_os, "linux-gnueabihf");
+        }
+
+        // Normalized CPU+vendor pairs that imply an OS, if not otherwise specified
+        "m68k-isi" => default_os(&mut basic_os, "sysv"),
+        "m68k-sony" => default_os(&mut basic_os, "newsos"),
+        "m68k-tektronix" => default_os(&mut basic_os, "bsd"),
+        "m88k-harris" => default_os(&mut basic_os, "sysv3"),
+        "i386-bull" | "m68k-bull" => default_os(&mut basic_os, "sysv3"),
+        "rs6000-bull" => default_os(&mut basic_os, "bosx"),
+        "mips-sni" => default_os(&mut basic_os, "sysv4"),
+
+        // Here we normalize CPU types irrespective of the vendor
+        s if s.starts_with("amd64-") => cpu = "x86_64".into(),
+        s if s.starts_with("blackfin-") => {
+            cpu = "bfin".into();
+            default_os(&mut basic_os, "linux");
+        }
+        s if s.starts_with("c54x-") => cpu = "tic54x".into(),
+        s if s.starts_with("c55x-") => cpu = "tic55x".into(),
+        s if s.starts_with("c6x-") => cpu = "tic6x".into(),
+        s if matches_glob!(s, "e500v[12]-*") => {
+            cpu = "powerpc".into();
+            basic_os.push_str("spe");
+        }
+        s if matches_glob!(s, "mips3*-*") => cpu = "mips64".into(),
+        s if s.starts_with("ms1-") => cpu = "mt".into(),
+        s if s.starts_with("m68knommu-") => {
+            cpu = "m68k".into();
+            default_os(&mut basic_os, "linux");
+        }
+        s if s.starts_with("m9s12z-")
+            || s.starts_with("m68hcs12z-")
+            || s.starts_with("hcs12z-")
+            || s.starts_with("s12z-") =>
+        {
+            cpu = "s12z".into();
+        }
+        s if s.starts_with("openrisc-") => cpu = "or32".into(),
+        s if s.starts_with("parisc-") => {
+            cpu = "hppa".into();
+            default_os(&mut basic_os, "linux");
+        }
+        s if s.starts_with("pentium-")
+            || s.starts_with("p5-")
+            || s.starts_with("k5-")
+            || s.starts_with("k6-")
+            || s.starts_with("nexgen-")
+            || s.starts_with("viac3-") =>
+        {
+            cpu = "i586".into();
+        }
+        s if s.starts_with("pentiumpro-")
+            || s.starts_with("p6-")
+            || s.starts_with("6x86-")
+            || s.starts_with("athlon-")
+            || matches_glob!(s, "athlon_*-*") =>
+        {
+            cpu = "i686".into();
+        }
+        s if s.starts_with("pentiumii-")
+            || s.starts_with("pentium2-")
+            || s.starts_with("pentiumiii-")
+            || s.starts_with("pentium3-") =>
+        {
+            cpu = "i686".into();
+        }
+        s if s.starts_with("pentium4-") => cpu = "i786".into(),
+        s if s.starts_with("ppc-") || s.starts_with("ppcbe-") => cpu = "powerpc".into(),
+        s if s.starts_with("ppcle-") || s.starts_with("powerpclittle-") => cpu = "powerpcle".into(),
+        s if s.starts_with("ppc64-") => cpu = "powerpc64".into(),
+        s if s.starts_with("ppc64le-") || s.starts_with("powerpc64little-") => cpu = "powerpc64le".into(),
+        s if s.starts_with("sb1-") => cpu = "mipsisa64sb1".into(),
+        s if s.starts_with("sb1el-") => cpu = "mipsisa64sb1el".into(),
+        s if matches_glob!(s, "sh5e[lb]-*") => cpu = format!("{}{}e", &cpu[..3], &cpu[4..]),
+        s if s.starts_with("spur-") => cpu = "spur".into(),
+        s if s.starts_with("strongarm-") || s.starts_with("thumb-") => cpu = "arm".into(),
+        s if s.starts_with("tx39-") => cpu = "mipstx39".into(),
+        s if s.starts_with("tx39el-") => cpu = "mipstx39el".into(),
+        s if s.starts_with("xscale-") || matches_glob!(s, "xscalee[bl]-*") => cpu = cpu.replacen("xscale", "arm", 1),
+        s if s.starts_with("arm64-") || s.starts_with("aarch64le-") || s.starts_with("arm64_32-") => {
+            cpu = "aarch64".into();
+        }
+
+        // Recognize the canonical CPU Types that limit and/or modify the
+        // company names they are paired with.
+        s if s.starts_with("cr16-") => default_os(&mut basic_os, "elf"),
+        s if s.starts_with("crisv32-") || matches_glob!(s, "etraxfs*-*") => {
+            cpu = "crisv32".into();
+            vendor = "axis".into();
+        }
+        s if s.starts_with("cris-") || matches_glob!(s, "etrax*-*") => {
+            cpu = "cris".into();
+            vendor = "axis".into();
+        }
+        s if s.starts_with("crx-") => default_os(&mut basic_os, "elf"),
+        "neo-tandem" => {
+            cpu = "neo".into();
+            vendor = "tandem".into();
+        }
+        "nse-tandem" => {
+            cpu = "nse".into();
+            vendor = "tandem".into();
+        }
+        "nsr-tandem" => {
+            cpu = "nsr".into();
+            vendor = "tandem".into();
+        }
+        "nsv-tandem" => {
+            cpu = "nsv".into();
+            vendor = "tandem".into();
+        }
+        "nsx-tandem" => {
+            cpu = "nsx".into();
+            vendor = "tandem".into();
+        }
+        "mipsallegrexel-sony" => {
+            cpu = "mipsallegrexel".into();
+            vendor = "sony".into();
+        }
+        s if matches_glob!(s, "tile*-*") => default_os(&mut basic_os, "linux-gnu"),
 
-        match vendor.as_str() {
-            s if s.starts_with("digital") => vendor = "dec".into(),
-            s if s.starts_with("commodore") => vendor = "cbm".into(),
+        // Recognize the canonical CPU types that are allowed with any
+        // company name. The Tandem ones are only valid with `tandem`, which
+        // is handled above.
+        _ => match Cpu::from(cpu.as_str()) {
+            Cpu::Neo | Cpu::Nse | Cpu::Nsr | Cpu::Nsv | Cpu::Nsx | Cpu::Other(_) => {
+                return Err(Error::MachineNotRecognized(machine));
+            }
             _ => {},
-        };
+        },
+    };
 
-        let (kernel, os, obj): (String, String, String) = if basic_os != "" {
-            let mut obj: String = "".into();
-
-            let (kernel, mut os): (String, String) = match basic_os.as_str() {
-                s if s.starts_with("gnu/linux") => ("linux".into(), s.replace("gnu/linux", "gnu").into()),
-                "os2-emx" => ("os2".into(), "emx".into()),
-                s if s.starts_with("nto-qnx") => ("nto".into(), s.replace("nto-qnx", "qnx").into()),
-                s if s.starts_with("nto") => ("nto".into(), s.replace("nto", "qnx").into()),
-                s if s.starts_with("ironclad") => ("ironclad".into(), s.replace("ironclad", "mlibc").into()),
-                s if s.starts_with("linux") => ("linux".into(), s.replace("linux", "gnu").into()),
-                s if s.starts_with("managarm") => ("managarm".into(), "mlibc".into()),
-                s => s.split_once('-').map(|(a, b)| (a.into(), b.into())).unwrap_or_else(|| ("".into(), s.into())),
-            };
-
-            match os.as_str() {
-                "auroraux" => os = "auroraux".into(),
-                s if s.starts_with("bluegene") => os = "cnk".into(),
-                s if s == "solaris1" || s.starts_with("solaris1.") => os = os.replace("solaris1", "sunos4").into(),
-                "solaris" => os = "solaris2".into(),
-                s if s.starts_with("unixware") => os = "sysv4.2uw".into(),
-                "ns" | "ns1" | "nextstep" | "nextstep1" | "openstep1" => os = "nextstep".into(),
-                "ns2" | "nextstep2" | "openstep2" => os = "nextstep2".into(),
-                "ns3" | "nextstep3" | "openstep" | "openstep3" => os = "openstep3".into(),
-                "ns4" | "nextstep4" | "openstep4" => os = "openstep4".into(),
-                s if s.starts_with("es1800") => os = "ose".into(),
-                s if s.starts_with("chorusos") => os = "chorusos".into(),
-                "isc" => os = "isc2.2".into(),
-                "sco6" => os = "sco5v6".into(),
-                "sco5" => os = "sco3.2v5".into(),
-                "sco4" => os = "sco3.2v4".into(),
-                s if matches_glob!(s, "sco3.2.[4-9]*") => os = s.replace("sco3.2.", "sco3.2v").into(),
-                s if matches_glob!(s, "sco*v*") || s == "scout" => {},
-                s if s.starts_with("sco") => os = "sco3.2v2".into(),
-                s if s.starts_with("psos") => os = "psos".into(),
-                _ => todo!(),
-                s if s.starts_with("pikeos") => match cpu.as_str() {
-                    s if s.starts_with("arm") => os = "eabi".into(),
-                    _ => {
-                        os = "".into();
-                        obj = "elf".into();
-                    },
-                },
-                s if s.starts_with("aout") || s.starts_with("coff") || s.starts_with("elf") || s.starts_with("pe") => {
-                    obj = os.clone();
-                    os = "".into();
-                },
-                _ => {},
-            };
+    match vendor.as_str() {
+        s if s.starts_with("digital") => vendor = "dec".into(),
+        s if s.starts_with("commodore") => vendor = "cbm".into(),
+        _ => {},
+    };
+
+    // Decode manufacturer-specific aliases for certain operating systems.
+    let (kernel, os, obj): (String, String, String) = if !basic_os.is_empty() {
+        let mut obj: String = "".into();
 
-            (kernel, os, obj)
-        } else {
-            todo!()
+        // First recognize some ad-hoc cases, or perhaps split kernel-os, or else just
+        // set os.
+        let (mut kernel, mut os): (String, String) = match basic_os.as_str() {
+            s if s.starts_with("gnu/linux") => ("linux".into(), s.replacen("gnu/linux", "gnu", 1)),
+            "os2-emx" => ("os2".into(), "emx".into()),
+            s if s.starts_with("nto-qnx") => ("nto".into(), s.replacen("nto-qnx", "qnx", 1)),
+            s if s.contains('-') => s.split_once('-').map(|(a, b)| (a.into(), b.into())).unwrap_or_default(),
+            // Default OS when just kernel was specified
+            s if s.starts_with("nto") => ("nto".into(), s.replacen("nto", "qnx", 1)),
+            s if s.starts_with("ironclad") => ("ironclad".into(), s.replacen("ironclad", "mlibc", 1)),
+            s if s.starts_with("linux") => ("linux".into(), s.replacen("linux", "gnu", 1)),
+            s if s.starts_with("managarm") => ("managarm".into(), s.replacen("managarm", "mlibc", 1)),
+            s => ("".into(), s.into()),
         };
 
+        // Now, normalize the OS (knowing we just have one component, it's not a kernel,
+        // etc.)
         match os.as_str() {
-            s if
-                s.starts_with("llvm")
-                || s.starts_with("musl")
-                || s.starts_with("newlibc")
-                || s.starts_with("relibc")
-                || s.starts_with("uclibc")
-                => {},
-            s if s.starts_with("eabi") || s.starts_with("gnueabi") => {}
-            "simlinux" | "simwindows" | "spe" => {},
-            "ghcjs" => {},
-            _ => todo!(),
-            "uefi" => {},
-            "none" => {},
-            s if s.starts_with("kernel") || s.starts_with("msvc") => {},
-            "" => {
-                if obj == "" {
-                    return Err(Error::BlankOsOnlyAllowedWithExplicitMachineCodeFileFormat);
+            "auroraux" => os = "auroraux".into(),
+            s if s.starts_with("bluegene") => os = "cnk".into(),
+            s if s == "solaris1" || s.starts_with("solaris1.") => os = os.replacen("solaris1", "sunos4", 1),
+            "solaris" => os = "solaris2".into(),
+            s if s.starts_with("unixware") => os = "sysv4.2uw".into(),
+            "ns" | "ns1" | "nextstep" | "nextstep1" | "openstep1" => os = "nextstep".into(),
+            "ns2" | "nextstep2" | "openstep2" => os = "nextstep2".into(),
+            "ns3" | "nextstep3" | "openstep" | "openstep3" => os = "openstep3".into(),
+            "ns4" | "nextstep4" | "openstep4" => os = "openstep4".into(),
+            s if s.starts_with("es1800") => os = "ose".into(),
+            s if s.starts_with("chorusos") => os = "chorusos".into(),
+            "isc" => os = "isc2.2".into(),
+            "sco6" => os = "sco5v6".into(),
+            "sco5" => os = "sco3.2v5".into(),
+            "sco4" => os = "sco3.2v4".into(),
+            s if matches_glob!(s, "sco3.2.[4-9]*") => os = s.replacen("sco3.2.", "sco3.2v", 1),
+            s if matches_glob!(s, "sco*v*") || s == "scout" => {},
+            s if s.starts_with("sco") => os = "sco3.2v2".into(),
+            s if s.starts_with("psos") => os = "psos".into(),
+            s if s.starts_with("qnx") => os = "qnx".into(),
+            s if s.starts_with("hiux") => os = "hiuxwe2".into(),
+            s if matches_glob!(s, "lynx*178") => os = "lynxos178".into(),
+            s if matches_glob!(s, "lynx*5") => os = "lynxos5".into(),
+            // don't get caught up in next wildcard
+            s if s.starts_with("lynxos") => {},
+            s if s.starts_with("lynx") => os = "lynxos".into(),
+            s if matches_glob!(s, "mac[0-9]*") => os = s.replacen("mac", "macos", 1),
+            s if s.starts_with("opened") => os = "openedition".into(),
+            s if s.starts_with("os400") => os = "os400".into(),
+            s if s.starts_with("sunos5") => os = s.replacen("sunos5", "solaris2", 1),
+            s if s.starts_with("sunos6") => os = s.replacen("sunos6", "solaris3", 1),
+            s if s.starts_with("wince") => os = "wince".into(),
+            s if s.starts_with("utek") => {
+                os = "bsd".into();
+                if vendor == "unknown" {
+                    vendor = "tektronix".into();
                 }
             },
-            _ => {
-                return Err(Error::OsNotRecognized(os));
-            }
+            s if s.starts_with("dynix") => os = "bsd".into(),
+            s if s.starts_with("acis") => os = "aos".into(),
+            s if s.starts_with("atheos") => os = "atheos".into(),
+            s if s.starts_with("syllable") => os = "syllable".into(),
+            "386bsd" => os = "bsd".into(),
+            s if s.starts_with("ctix") => {
+                os = "sysv".into();
+                if vendor == "unknown" {
+                    vendor = "convergent".into();
+                }
+            },
+            s if s.starts_with("uts") => os = "sysv".into(),
+            s if s.starts_with("nova") => {
+                kernel = "rtmk".into();
+                os = "nova".into();
+            },
+            // Preserve the version number of sinix5.
+            s if s.starts_with("sinix5.") => {
+                os = s.replacen("sinix", "sysv", 1);
+                if vendor == "unknown" {
+                    vendor = "sni".into();
+                }
+            },
+            s if s.starts_with("sinix") => {
+                os = "sysv4".into();
+                if vendor == "unknown" {
+                    vendor = "sni".into();
+                }
+            },
+            s if s.starts_with("tpf") => os = "tpf".into(),
+            s if s.starts_with("triton") => os = "sysv3".into(),
+            s if s.starts_with("oss") => os = "sysv3".into(),
+            s if s.starts_with("svr4") => os = "sysv4".into(),
+            "svr3" => os = "sysv3".into(),
+            "sysvr4" => os = "sysv4".into(),
+            s if s.starts_with("ose") => os = "ose".into(),
+            s if s.ends_with("mint")
+                || matches_glob!(s, "mint[0-9]*")
+                || s.ends_with("MiNT")
+                || matches_glob!(s, "MiNT[0-9]*") =>
+            {
+                os = "mint".into();
+            },
+            s if s.starts_with("dicos") => os = "dicos".into(),
+            // Until real need of OS specific support for
+            // particular features comes up, bare metal
+            // configurations are quite functional.
+            s if s.starts_with("pikeos") => match cpu.as_str() {
+                s if s.starts_with("arm") => os = "eabi".into(),
+                _ => {
+                    os = "".into();
+                    obj = "elf".into();
+                },
+            },
+            s if s.starts_with("aout") || s.starts_with("coff") || s.starts_with("elf") || s.starts_with("pe") => {
+                obj = os.clone();
+                os = "".into();
+            },
+            _ => {},
         };
 
-        match obj.as_str() {
-            s if s.starts_with("aout") || s.starts_with("coff") || s.starts_with("elf") || s.starts_with("pe") => {},
-            "" => {},
-            _ => {
-                return Err(Error::MachineCodeFormatNotRecognized(obj));
+        (kernel, os, obj)
+    } else {
+        // Here we handle the default operating systems that come with various machines.
+        // The value should be what the vendor currently ships out the door with their
+        // machine or put another way, the most popular os provided with the machine.
+
+        // Note that if you're going to try to match "-MANUFACTURER" here (say,
+        // "-sun"), then you have to tell the case statement up towards the top
+        // that MANUFACTURER isn't an operating system.  Otherwise, code above
+        // will signal an error saying that MANUFACTURER isn't an operating
+        // system, and we'll never get to this point.
+        let machine = format!("{cpu}-{vendor}");
+        let (kernel, os, obj) = match machine.as_str() {
+            s if s.starts_with("score-") => ("", "", "elf"),
+            s if s.starts_with("spu-") => ("", "", "elf"),
+            s if s.ends_with("-acorn") => ("", "riscix1.2", ""),
+            s if matches_glob!(s, "arm*-rebel") => ("linux", "gnu", ""),
+            s if matches_glob!(s, "arm*-semi") => ("", "", "aout"),
+            s if s.starts_with("c4x-") || s.starts_with("tic4x-") => ("", "", "coff"),
+            s if s.starts_with("c8051-") => ("", "", "elf"),
+            "clipper-intergraph" => ("", "clix", ""),
+            s if s.starts_with("hexagon-") => ("", "", "elf"),
+            s if s.starts_with("tic54x-") => ("", "", "coff"),
+            s if s.starts_with("tic55x-") => ("", "", "coff"),
+            s if s.starts_with("tic6x-") => ("", "", "coff"),
+            // This must come before the *-dec entry.
+            s if s.starts_with("pdp10-") => ("", "tops20", ""),
+            s if s.starts_with("pdp11-") => ("", "none", ""),
+            s if s.ends_with("-dec") || s.starts_with("vax-") => ("", "ultrix4.2", ""),
+            s if matches_glob!(s, "m68*-apollo") => ("", "domain", ""),
+            "i386-sun" => ("", "sunos4.0.2", ""),
+            "m68000-sun" => ("", "sunos3", ""),
+            s if matches_glob!(s, "m68*-cisco") => ("", "", "aout"),
+            s if s.starts_with("mep-") => ("", "", "elf"),
+            // The -sgi and -siemens entries must be before the mips- entry
+            // or we get the wrong os.
+            s if s.ends_with("-sgi") => ("", "irix", ""),
+            s if s.ends_with("-siemens") => ("", "sysv4", ""),
+            s if matches_glob!(s, "mips*-cisco") => ("", "", "elf"),
+            s if matches_glob!(s, "mips*-*") || matches_glob!(s, "nanomips*-*") => ("", "", "elf"),
+            s if s.starts_with("or32-") => ("", "", "coff"),
+            // This must be before the sparc-* entry or we get the wrong os.
+            s if s.ends_with("-tti") => ("", "sysv3", ""),
+            s if s.starts_with("sparc-") || s.ends_with("-sun") => ("", "sunos4.1.1", ""),
+            s if s.starts_with("pru-") => ("", "", "elf"),
+            s if s.ends_with("-be") => ("", "beos", ""),
+            s if s.ends_with("-ibm") => ("", "aix", ""),
+            s if s.ends_with("-knuth") => ("", "mmixware", ""),
+            s if s.ends_with("-wec") => ("", "proelf", ""),
+            s if s.ends_with("-winbond") => ("", "proelf", ""),
+            s if s.ends_with("-oki") => ("", "proelf", ""),
+            s if s.ends_with("-hp") => ("", "hpux", ""),
+            s if s.ends_with("-hitachi") => ("", "hiuxwe2", ""),
+            s if s.starts_with("i860-")
+                || s.ends_with("-att")
+                || s.ends_with("-ncr")
+                || s.ends_with("-altos")
+                || s.ends_with("-motorola")
+                || s.ends_with("-convergent") =>
+            {
+                ("", "sysv", "")
             }
+            s if s.ends_with("-cbm") => ("", "amigaos", ""),
+            s if s.ends_with("-dg") => ("", "dgux", ""),
+            s if s.ends_with("-dolphin") => ("", "sysv3", ""),
+            "m68k-ccur" => ("", "rtu", ""),
+            s if s.starts_with("m88k-omron") => ("", "luna", ""),
+            s if s.ends_with("-next") => ("", "nextstep", ""),
+            s if s.ends_with("-sequent") => ("", "ptx", ""),
+            s if s.ends_with("-crds") => ("", "unos", ""),
+            s if s.ends_with("-ns") => ("", "genix", ""),
+            s if s.starts_with("i370-") => ("", "mvs", ""),
+            s if s.ends_with("-gould") => ("", "sysv", ""),
+            s if s.ends_with("-highlevel") => ("", "bsd", ""),
+            s if s.ends_with("-encore") => ("", "bsd", ""),
+            s if s.ends_with("-masscomp") => ("", "rtu", ""),
+            s if matches_glob!(s, "f30[01]-fujitsu") || s == "f700-fujitsu" => ("", "uxpv", ""),
+            s if s.ends_with("-rom68k") => ("", "", "coff"),
+            s if matches_glob!(s, "*-*bug") => ("", "", "coff"),
+            s if s.ends_with("-apple") => ("", "macos", ""),
+            s if matches_glob!(s, "*-atari*") => ("", "mint", ""),
+            s if s.ends_with("-wrs") => ("", "vxworks", ""),
+            _ => ("", "none", ""),
         };
 
-        match (cpu.as_str(), os.as_str()) {
-            ("javascript", "ghcjs") => {},
-            ("javascript", _) | (_, "ghcjs") => {
-                return Err(Error::CpuIsNotValidWithOs(cpu, os));
-            },
-            _ => {},
-        };
+        (kernel.into(), os.into(), obj.into())
+    };
 
-        match (cpu.as_str(), os.as_str(), obj.as_str()) {
-            ("linux", s, "") if
-                s.starts_with("gnu")
-                || s.starts_with("android")
-                || s.starts_with("dietlibc")
-                || s.starts_with("llvm")
-                || s.starts_with("mlibc")
-                || s.starts_with("musl")
-                || s.starts_with("newlib")
-                || s.starts_with("relibc")
-                || s.starts_with("uclibc")
-                || s.starts_with("ohos")
-             => {},
-            ("uclinux", s, "") if s.starts_with("uclibc") || s.starts_with("gnu") => {},
-            ("ironclad", s, "") if s.starts_with("mlibc") => {},
-            _ => todo!(),
-            ("", s, "") if s.starts_with("kernel") => return Err(Error::OsNeedsExplicitKernel(os)),
-            (_, s, "") if s.starts_with("kernel") => return Err(Error::KernelDoesNotSupportOs(kernel, os)),
-            (_, s, "") if s.starts_with("msvc") => return Err(Error::OsNeedsWindows(os)),
-            _ => todo!(),
-            ("none", "", _) => {},
-            ("", _, "") => {},
-            ("", "", _) => {},
-            (_, _, _) => return Err(Error::KernelNotKnownToWorkWithOs(kernel, os)),
-        };
+    match os.as_str() {
+        s if
+            s.starts_with("llvm")
+            || s.starts_with("musl")
+            || s.starts_with("newlib")
+            || s.starts_with("relibc")
+            || s.starts_with("uclibc")
+            => {},
+        s if s.starts_with("eabi") || s.starts_with("gnueabi") => {}
+        "simlinux" | "simwindows" | "spe" => {},
+        "ghcjs" => {},
+        // Now accept the basic system types, which `Os` knows along with
+        // any version number after them.
+        s if Os::from(s).is_known() || s.contains("vms") => {},
+        "uefi" => {},
+        "none" => {},
+        s if s.starts_with("kernel") || s.starts_with("msvc") => {},
+        "" => {
+            if obj.is_empty() {
+                return Err(Error::BlankOsOnlyAllowedWithExplicitMachineCodeFileFormat);
+            }
+        },
+        _ => {
+            return Err(Error::OsNotRecognized(os));
+        }
+    };
 
-        if vendor == "unknown" {
-            match (cpu.as_str(), os.as_str()) {
-                _ => todo!(),
-            };
+    match obj.as_str() {
+        s if s.starts_with("aout") || s.starts_with("coff") || s.starts_with("elf") || s.starts_with("pe") => {},
+        "" => {},
+        _ => {
+            return Err(Error::MachineCodeFormatNotRecognized(obj));
         }
+    };
 
-        Ok(format!(
-            "{}-{}{}{}{}",
-            cpu,
-            vendor,
-            if kernel == "" { "".into() } else { format!("-{}", kernel) },
-            if os == "" { "".into() } else { format!("-{}", os) },
-            if obj == "" { "".into() } else { format!("-{}", obj) },
-        ))
+    match (cpu.as_str(), os.as_str()) {
+        ("javascript", "ghcjs") => {},
+        ("javascript", _) | (_, "ghcjs") => {
+            return Err(Error::CpuIsNotValidWithOs(cpu, format!("{os}{obj}")));
+        },
+        _ => {},
+    };
+
+    // As a final step for OS-related things, validate the OS-kernel combination
+    // (given a valid OS), if there is a kernel.
+    match (kernel.as_str(), os.as_str(), obj.as_str()) {
+        ("linux", s, "") if
+            s.starts_with("gnu")
+            || s.starts_with("android")
+            || s.starts_with("dietlibc")
+            || s.starts_with("llvm")
+            || s.starts_with("mlibc")
+            || s.starts_with("musl")
+            || s.starts_with("newlib")
+            || s.starts_with("relibc")
+            || s.starts_with("uclibc")
+            || s.starts_with("ohos")
+         => {},
+        ("uclinux", s, "") if s.starts_with("uclibc") || s.starts_with("gnu") => {},
+        ("ironclad", s, "") if s.starts_with("mlibc") => {},
+        ("managarm", s, "") if s.starts_with("mlibc") || s.starts_with("kernel") => {},
+        (k, s, "") if k.starts_with("windows") && s.starts_with("msvc") => {},
+        ("", s, "") if
+            s.starts_with("dietlibc")
+            || s.starts_with("llvm")
+            || s.starts_with("mlibc")
+            || s.starts_with("musl")
+            || s.starts_with("newlib")
+            || s.starts_with("relibc")
+            || s.starts_with("uclibc")
+         => {
+            // These are just libc implementations, not actual OSes, and thus
+            // require a kernel.
+            return Err(Error::LibcNeedsExplicitKernel(os));
+        },
+        ("", s, "") if s.starts_with("kernel") => return Err(Error::OsNeedsExplicitKernel(os)),
+        (_, s, "") if s.starts_with("kernel") => return Err(Error::KernelDoesNotSupportOs(kernel, os)),
+        (_, s, "") if s.starts_with("msvc") => return Err(Error::OsNeedsWindows(os)),
+        (k, s, "") if
+            (k.starts_with("kfreebsd")
+                || k.starts_with("knetbsd")
+                || k.starts_with("netbsd")
+                || k.starts_with("kopensolaris"))
+            && s.starts_with("gnu")
+         => {},
+        ("vxworks", "simlinux" | "simwindows" | "spe", "") => {},
+        ("nto", s, "") if s.starts_with("qnx") => {},
+        ("os2", "emx", "") => {},
+        ("rtmk", "nova", "") => {},
+        (_, s, "") if s.starts_with("eabi") || s.starts_with("gnueabi") => {},
+        (k, "simulator", "") if k.starts_with("ios") || k.starts_with("tvos") || k.starts_with("watchos") => {},
+        // None (no kernel, i.e. freestanding / bare metal),
+        // can be paired with an machine code file format
+        ("none", "", _) => {},
+        // Blank kernel with real OS is always fine.
+        ("", _, "") => {},
+        // Blank kernel and OS with real machine code file format is always fine.
+        ("", "", _) => {},
+        (_, _, _) => return Err(Error::KernelNotKnownToWorkWithOs(kernel, os)),
+    };
+
+    // Here we handle the case where we know the os, and the CPU type, but not the
+    // manufacturer.  We pick the logical manufacturer.
+    if vendor == "unknown" {
+        let logical_vendor = match (cpu.as_str(), os.as_str()) {
+            (_, s) if s.starts_with("riscix") => Some("acorn"),
+            (_, s) if s.starts_with("sunos") || s.starts_with("solaris") => Some("sun"),
+            (_, s) if s.starts_with("cnk") || s.starts_with("aix") => Some("ibm"),
+            (_, s) if s.starts_with("beos") => Some("be"),
+            (_, s) if s.starts_with("hpux") => Some("hp"),
+            (_, s) if s.starts_with("mpeix") => Some("hp"),
+            (_, s) if s.starts_with("hiux") => Some("hitachi"),
+            (_, s) if s.starts_with("unos") => Some("crds"),
+            (_, s) if s.starts_with("dgux") => Some("dg"),
+            (_, s) if s.starts_with("luna") => Some("omron"),
+            (_, s) if s.starts_with("genix") => Some("ns"),
+            (_, s) if s.starts_with("clix") => Some("intergraph"),
+            (_, s) if s.starts_with("mvs") || s.starts_with("opened") => Some("ibm"),
+            (_, s) if s.starts_with("os400") => Some("ibm"),
+            ("s390" | "s390x", _) => Some("ibm"),
+            (_, s) if s.starts_with("ptx") => Some("sequent"),
+            (_, s) if s.starts_with("tpf") => Some("ibm"),
+            (_, s) if s.starts_with("vxsim") || s.starts_with("vxworks") || s.starts_with("windiss") => Some("wrs"),
+            (_, s) if s.starts_with("aux") => Some("apple"),
+            (_, s) if s.starts_with("hms") => Some("hitachi"),
+            (_, s) if s.starts_with("mpw") || s.starts_with("macos") => Some("apple"),
+            (_, s) if s.ends_with("mint")
+                || matches_glob!(s, "mint[0-9]*")
+                || s.ends_with("MiNT")
+                || matches_glob!(s, "MiNT[0-9]*") =>
+            {
+                Some("atari")
+            }
+            (_, s) if s.starts_with("vos") => Some("stratus"),
+            _ => None,
+        };
+        if let Some(logical_vendor) = logical_vendor {
+            vendor = logical_vendor.into();
+        }
     }
-    inner(input.as_ref())
+
+    Ok(Triple {
+        cpu: Cpu::from(cpu.as_str()),
+        vendor: Vendor::from(vendor.as_str()),
+        kernel: Some(kernel.as_str()).filter(|s| !s.is_empty()).map(Kernel::from),
+        os: Some(os.as_str()).filter(|s| !s.is_empty()).map(Os::from),
+        obj: Some(obj.as_str()).filter(|s| !s.is_empty()).map(Obj::from),
+    })
 }
@@ -0,0 +1,10 @@
+component! {
+    /// The machine code file format of a configuration name.
+    pub enum Obj {
+        ;
+        Aout { variant } = "aout" *,
+        Coff { variant } = "coff" *,
+        Elf { variant } = "elf" *,
+        Pe { variant } = "pe" *,
+    }
+}
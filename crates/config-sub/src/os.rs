@@ -0,0 +1,218 @@
+component! {
+    /// The operating system of a configuration name.
+    ///
+    /// These are the base systems config.sub accepts, along with the libc
+    /// and ABI names it allows in their place after a kernel. Everything
+    /// after the name is kept as the payload, e.g. `freebsd14.1` is
+    /// `Os::FreeBsd { version: "14.1".into() }` and `gnueabihf` is
+    /// `Os::Gnu { abi: "eabihf".into() }`.
+    pub enum Os {
+        Abug = "abug",
+        Ghcjs = "ghcjs",
+        None = "none",
+        Nx6 = "nx6",
+        Nx7 = "nx7",
+        SimLinux = "simlinux",
+        Simulator = "simulator",
+        SimWindows = "simwindows",
+        Spe = "spe",
+        Uefi = "uefi",
+        ;
+        Aix { version } = "aix" *,
+        Amdhsa { version } = "amdhsa" *,
+        AmigaDos { version } = "amigados" *,
+        AmigaOs { version } = "amigaos" *,
+        Android { version } = "android" *,
+        Aof { version } = "aof" *,
+        Aos { version } = "aos" *,
+        Aros { version } = "aros" *,
+        AtheOs { version } = "atheos" *,
+        AuroraUx { version } = "auroraux" *,
+        Aux { version } = "aux" *,
+        BananOs { version } = "banan_os" *,
+        BeOs { version } = "beos" *,
+        Bitrig { version } = "bitrig" *,
+        Bme { version } = "bme" *,
+        Bosx { version } = "bosx" *,
+        Bsd { version } = "bsd" *,
+        Cegcc { version } = "cegcc" *,
+        ChorusOs { version } = "chorusos" *,
+        ChorusRdb { version } = "chorusrdb" *,
+        Clix { version } = "clix" *,
+        CloudAbi { version } = "cloudabi" *,
+        Cnk { version } = "cnk" *,
+        Conix { version } = "conix" *,
+        Cos { version } = "cos" *,
+        Cxux { version } = "cxux" *,
+        Cygwin { version } = "cygwin" *,
+        Darwin { version } = "darwin" *,
+        Dgux { version } = "dgux" *,
+        Dicos { version } = "dicos" *,
+        Dnix { version } = "dnix" *,
+        Domain { version } = "domain" *,
+        DragonFly { version } = "dragonfly" *,
+        Drops { version } = "drops" *,
+        Eabi { abi } = "eabi" *,
+        Ebmon { version } = "ebmon" *,
+        Ecoff { version } = "ecoff" *,
+        EkkoBsd { version } = "ekkobsd" *,
+        Emscripten { version } = "emscripten" *,
+        Emx { version } = "emx" *,
+        Es { version } = "es" *,
+        Fiwix { version } = "fiwix" *,
+        FreeBsd { version } = "freebsd" *,
+        Fuchsia { version } = "fuchsia" *,
+        Genix { version } = "genix" *,
+        Genode { version } = "genode" *,
+        Glidix { version } = "glidix" *,
+        Gnu { abi } = "gnu" *,
+        Go32 { version } = "go32" *,
+        Haiku { version } = "haiku" *,
+        Hcos { version } = "hcos" *,
+        Hiux { version } = "hiux" *,
+        Hms { version } = "hms" *,
+        Hpux { version } = "hpux" *,
+        Ieee { version } = "ieee" *,
+        Interix { version } = "interix" *,
+        Ios { version } = "ios" *,
+        Iris { version } = "iris" *,
+        Irix { version } = "irix" *,
+        Isc { version } = "isc" *,
+        Its { version } = "its" *,
+        Kernel { version } = "kernel" *,
+        L4re { version } = "l4re" *,
+        LibertyBsd { version } = "libertybsd" *,
+        Lites { version } = "lites" *,
+        Llvm { abi } = "llvm" *,
+        Lnews { version } = "lnews" *,
+        Luna { version } = "luna" *,
+        LynxOs { version } = "lynxos" *,
+        Mach { version } = "mach" *,
+        MacOs { version } = "macos" *,
+        Magic { version } = "magic" *,
+        Mbr { version } = "mbr" *,
+        Midipix { version } = "midipix" *,
+        MidnightBsd { version } = "midnightbsd" *,
+        Mingw32 { version } = "mingw32" *,
+        Mingw32ce { version } = "mingw32ce" *,
+        Mingw64 { version } = "mingw64" *,
+        Minix { version } = "minix" *,
+        Mint { version } = "mint" *,
+        MirBsd { version } = "mirbsd" *,
+        Mks { version } = "mks" *,
+        Mlibc { abi } = "mlibc" *,
+        Mmixware { version } = "mmixware" *,
+        Mon960 { version } = "mon960" *,
+        MorphOs { version } = "morphos" *,
+        Moss { version } = "moss" *,
+        Moxiebox { version } = "moxiebox" *,
+        Mpeix { version } = "mpeix" *,
+        Mpw { version } = "mpw" *,
+        MsDos { version } = "msdos" *,
+        Msvc { version } = "msvc" *,
+        Msys { version } = "msys" *,
+        Musl { abi } = "musl" *,
+        Mvs { version } = "mvs" *,
+        Nacl { version } = "nacl" *,
+        NetBsd { version } = "netbsd" *,
+        Netware { version } = "netware" *,
+        Newlib { abi } = "newlib" *,
+        NewsOs { version } = "newsos" *,
+        NextStep { version } = "nextstep" *,
+        Nindy { version } = "nindy" *,
+        NonStopUx { version } = "nonstopux" *,
+        Nova { version } = "nova" *,
+        Nsk { version } = "nsk" *,
+        Nucleus { version } = "nucleus" *,
+        Oabi { abi } = "oabi" *,
+        Ohos { version } = "ohos" *,
+        OneFs { version } = "onefs" *,
+        OpenBsd { version } = "openbsd" *,
+        OpenEdition { version } = "openedition" *,
+        OpenStep { version } = "openstep" *,
+        OpenVms { version } = "openvms" *,
+        Os108 { version } = "os108" *,
+        Os2 { version } = "os2" *,
+        Os400 { version } = "os400" *,
+        Os68k { version } = "os68k" *,
+        Os9 { version } = "os9" *,
+        Ose { version } = "ose" *,
+        Osf { version } = "osf" *,
+        OsKit { version } = "oskit" *,
+        Osx { version } = "osx" *,
+        PalmOs { version } = "palmos" *,
+        Phoenix { version } = "phoenix" *,
+        Plan9 { version } = "plan9" *,
+        PowerMax { version } = "powermax" *,
+        PowerUnix { version } = "powerunix" *,
+        ProElf { version } = "proelf" *,
+        Psos { version } = "psos" *,
+        Psp { version } = "psp" *,
+        Ptx { version } = "ptx" *,
+        Pw32 { version } = "pw32" *,
+        Qnx { version } = "qnx" *,
+        Rdos { version } = "rdos" *,
+        Redox { version } = "redox" *,
+        Relibc { abi } = "relibc" *,
+        Rhapsody { version } = "rhapsody" *,
+        Riscix { version } = "riscix" *,
+        RiscOs { version } = "riscos" *,
+        Rtems { version } = "rtems" *,
+        Rtmk { version } = "rtmk" *,
+        Rtu { version } = "rtu" *,
+        /// Only the SCO releases config.sub accepts, which keep their version
+        /// if it is 3.2v4 or newer.
+        Sco { version } = "sco" * if ["sco3.2v2", "sco3.2v[4-9]*", "sco5v6*"],
+        Scout { version } = "scout" *,
+        SecBsd { version } = "secbsd" *,
+        Sei { version } = "sei" *,
+        Serenity { version } = "serenity" *,
+        Sim { version } = "sim" *,
+        SkyOs { version } = "skyos" *,
+        Solaris { version } = "solaris" *,
+        SolidBsd { version } = "solidbsd" *,
+        Sortix { version } = "sortix" *,
+        StormChaos { version } = "storm-chaos" *,
+        SunOs { version } = "sunos" * if ["sunos", "sunos[34]*"],
+        SuperUx { version } = "superux" *,
+        Syllable { version } = "syllable" *,
+        Sym { version } = "sym" *,
+        Sysv { version } = "sysv" *,
+        Tenex { version } = "tenex" *,
+        TiRtos { version } = "tirtos" *,
+        Tock { version } = "tock" *,
+        Toppers { version } = "toppers" *,
+        Tops10 { version } = "tops10" *,
+        Tops20 { version } = "tops20" *,
+        Tpf { version } = "tpf" *,
+        TvOs { version } = "tvos" *,
+        Twizzler { version } = "twizzler" *,
+        Uclibc { abi } = "uclibc" *,
+        Uclinux { version } = "uclinux" *,
+        Udi { version } = "udi" *,
+        Udk { version } = "udk" *,
+        Ultrix { version } = "ultrix" *,
+        Unicos { version } = "unicos" *,
+        Uniplus { version } = "uniplus" *,
+        Unleashed { version } = "unleashed" *,
+        Unos { version } = "unos" *,
+        Uwin { version } = "uwin" *,
+        Uxpv { version } = "uxpv" *,
+        V88r { version } = "v88r" *,
+        Vms { version } = "vms" *,
+        Vos { version } = "vos" *,
+        Vsta { version } = "vsta" *,
+        VxSim { version } = "vxsim" *,
+        VxWorks { version } = "vxworks" *,
+        Wasi { version } = "wasi" *,
+        WatchOs { version } = "watchos" *,
+        WinCe { version } = "wince" *,
+        Windiss { version } = "windiss" *,
+        Windows { version } = "windows" *,
+        WinNt { version } = "winnt" *,
+        Xenix { version } = "xenix" *,
+        Xray { version } = "xray" *,
+        Zephyr { version } = "zephyr" *,
+        Zvmoe { version } = "zvmoe" *,
+    }
+}
@@ -0,0 +1,73 @@
+use std::{fmt, str::FromStr};
+
+use crate::{Cpu, Error, Kernel, Obj, Os, Vendor, canonicalize};
+
+/// A configuration name split into its components.
+///
+/// Parsing a `Triple` with [`FromStr`] runs the full canonicalization. The
+/// [`Display`](fmt::Display) form is the canonical
+/// `CPU-VENDOR[-KERNEL][-OS][-OBJ]` string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Triple {
+    pub cpu: Cpu,
+    pub vendor: Vendor,
+    pub kernel: Option<Kernel>,
+    pub os: Option<Os>,
+    pub obj: Option<Obj>,
+}
+
+impl Triple {
+    /// Split a configuration name into its fields as-is, without
+    /// canonicalizing or validating it.
+    ///
+    /// Two fields are `CPU-VENDOR`, three are `CPU-VENDOR-OS` and four are
+    /// `CPU-VENDOR-KERNEL-OS`. Anything past the fourth dash stays in the OS.
+    pub fn from_fields(input: &str) -> Self {
+        let mut fields = input.splitn(4, '-');
+        let cpu = Cpu::from(fields.next().unwrap_or_default());
+        let vendor = Vendor::from(fields.next().unwrap_or_default());
+        let (kernel, os) = match (fields.next(), fields.next()) {
+            (Some(kernel), Some(os)) => (Some(Kernel::from(kernel)), Some(Os::from(os))),
+            (os, _) => (None, os.map(Os::from)),
+        };
+        Self {
+            cpu,
+            vendor,
+            kernel,
+            os,
+            obj: None,
+        }
+    }
+}
+
+impl fmt::Display for Triple {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.cpu)?;
+        // A one-field local name has no vendor.
+        if self.vendor != Vendor::Other(String::new()) {
+            write!(f, "-{}", self.vendor)?;
+        }
+        if let Some(kernel) = &self.kernel {
+            write!(f, "-{}", kernel)?;
+        }
+        if let Some(os) = &self.os {
+            write!(f, "-{}", os)?;
+        }
+        if let Some(obj) = &self.obj {
+            write!(f, "-{}", obj)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Triple {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains("local") {
+            // Local machine types are passed through untouched.
+            return Ok(Self::from_fields(s));
+        }
+        canonicalize(s)
+    }
+}
@@ -0,0 +1,99 @@
+component! {
+    /// The manufacturer of a configuration name.
+    ///
+    /// config.sub allows any company name, so only the ones it knows about
+    /// have their own variant.
+    pub enum Vendor {
+        Acorn = "acorn",
+        Alliant = "alliant",
+        Alt = "alt",
+        Altos = "altos",
+        Amd = "amd",
+        Amdahl = "amdahl",
+        Apollo = "apollo",
+        Apple = "apple",
+        Atari = "atari",
+        Att = "att",
+        Auspex = "auspex",
+        Axis = "axis",
+        Be = "be",
+        Bull = "bull",
+        Cbm = "cbm",
+        Ccur = "ccur",
+        Cisco = "cisco",
+        Compaq = "compaq",
+        Convergent = "convergent",
+        Convex = "convex",
+        Cray = "cray",
+        Crds = "crds",
+        Cydrome = "cydrome",
+        Da30 = "da30",
+        Dde = "dde",
+        Dec = "dec",
+        Dg = "dg",
+        Diab = "diab",
+        Dolphin = "dolphin",
+        Elxsi = "elxsi",
+        Encore = "encore",
+        Ericsson = "ericsson",
+        Fujitsu = "fujitsu",
+        Gould = "gould",
+        Hades = "hades",
+        Hal = "hal",
+        Harris = "harris",
+        Highlevel = "highlevel",
+        Hitachi = "hitachi",
+        Honeywell = "honeywell",
+        Hp = "hp",
+        Ibm = "ibm",
+        Icl = "icl",
+        Intel = "intel",
+        Intergraph = "intergraph",
+        Isi = "isi",
+        Knuth = "knuth",
+        Mach = "mach",
+        Masscomp = "masscomp",
+        Milan = "milan",
+        Mips = "mips",
+        Motorola = "motorola",
+        Ncr = "ncr",
+        Nec = "nec",
+        Next = "next",
+        None = "none",
+        Ns = "ns",
+        Nyu = "nyu",
+        Oki = "oki",
+        Omron = "omron",
+        Pc = "pc",
+        Pc532 = "pc532",
+        Pyramid = "pyramid",
+        Rebel = "rebel",
+        Rom68k = "rom68k",
+        Sde = "sde",
+        Sei = "sei",
+        Semi = "semi",
+        Sequent = "sequent",
+        Sgi = "sgi",
+        Siemens = "siemens",
+        Sni = "sni",
+        Sony = "sony",
+        Stardent = "stardent",
+        Stratus = "stratus",
+        Sun = "sun",
+        Tandem = "tandem",
+        Tektronix = "tektronix",
+        Tti = "tti",
+        Unisys = "unisys",
+        Univel = "univel",
+        Unknown = "unknown",
+        Utek = "utek",
+        W64 = "w64",
+        Wdc = "wdc",
+        Wec = "wec",
+        Winbond = "winbond",
+        Wrs = "wrs",
+        Xilinx = "xilinx",
+        Xkl = "xkl",
+        ;
+    }
+}
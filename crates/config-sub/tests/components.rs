@@ -0,0 +1,77 @@
+use config_sub::{Cpu, Kernel, Obj, Os, Triple, Vendor};
+
+#[test]
+fn version_suffixes_are_kept_as_payload() {
+    assert_eq!(Os::from("freebsd14.1"), Os::FreeBsd { version: "14.1".into() });
+    assert_eq!(Os::from("darwin23.4.0"), Os::Darwin { version: "23.4.0".into() });
+    assert_eq!(Os::from("sco3.2v5"), Os::Sco { version: "3.2v5".into() });
+    assert_eq!(Os::from("android21"), Os::Android { version: "21".into() });
+    assert_eq!(Os::from("gnueabihf"), Os::Gnu { abi: "eabihf".into() });
+    assert_eq!(Cpu::from("i686"), Cpu::I86 { generation: "6".into() });
+    assert_eq!(Kernel::from("kfreebsd10.1"), Kernel::KFreeBsd { version: "10.1".into() });
+    assert_eq!(Obj::from("elf"), Obj::Elf { variant: "".into() });
+}
+
+#[test]
+fn glob_guards_restrict_prefix_variants() {
+    assert_eq!(Os::from("sco3.2v2"), Os::Sco { version: "3.2v2".into() });
+    assert_eq!(Os::from("sco5v6.0.0"), Os::Sco { version: "5v6.0.0".into() });
+    assert!(!matches!(Os::from("sco3.2v3"), Os::Sco { .. }));
+    assert_eq!(Os::from("sunos"), Os::SunOs { version: "".into() });
+    assert_eq!(Os::from("sunos4.1.1"), Os::SunOs { version: "4.1.1".into() });
+    assert!(!matches!(Os::from("sunos5.11"), Os::SunOs { .. }));
+    assert_eq!(Cpu::from("c2"), Cpu::Convex { model: "2".into() });
+    assert!(!matches!(Cpu::from("c4x"), Cpu::Convex { .. }));
+    assert!(!matches!(Cpu::from("hppa3.0"), Cpu::Hppa { .. }));
+}
+
+#[test]
+fn unrecognized_names_are_other() {
+    assert_eq!(Vendor::from("local"), Vendor::Other("local".into()));
+    assert!(!Vendor::from("local").is_known());
+    assert!(Vendor::from("pc").is_known());
+}
+
+#[test]
+fn components_round_trip() {
+    for os in ["freebsd14.1", "darwin23.4.0", "sco3.2v5", "android21", "gnueabihf", "none", "sco3.2v3", "local"] {
+        assert_eq!(os.parse::<Os>().map(|os| os.to_string()), Ok(os.into()), "{os}");
+    }
+    for cpu in ["x86_64", "i686", "c2", "hppa2.0w", "sh4", "mips64el", "armv7l", "local"] {
+        assert_eq!(cpu.parse::<Cpu>().map(|cpu| cpu.to_string()), Ok(cpu.into()), "{cpu}");
+    }
+}
+
+#[test]
+fn triples_round_trip() {
+    for triple in [
+        "x86_64-pc-linux-gnu",
+        "arm-unknown-linux-gnueabihf",
+        "sparc-sun-solaris2.11",
+        "aarch64-apple-darwin23.4.0",
+        "x86_64-pc-none-elf",
+        "x86_64-pc-kfreebsd-gnu",
+    ] {
+        assert_eq!(triple.parse::<Triple>().map(|triple| triple.to_string()), Ok(triple.into()), "{triple}");
+    }
+}
+
+#[test]
+fn triple_fields() {
+    let triple = "x86_64-pc-linux-gnu".parse::<Triple>().expect("valid triple");
+    assert_eq!(triple.cpu, Cpu::X86_64);
+    assert_eq!(triple.vendor, Vendor::Pc);
+    assert_eq!(triple.kernel, Some(Kernel::Linux));
+    assert_eq!(triple.os, Some(Os::Gnu { abi: "".into() }));
+    assert_eq!(triple.obj, None);
+}
+
+#[test]
+fn local_names_pass_through() {
+    for local in ["local", "x86-local", "local-x-y-z-w"] {
+        assert_eq!(local.parse::<Triple>().map(|triple| triple.to_string()), Ok(local.into()), "{local}");
+    }
+    let triple = Triple::from_fields("local");
+    assert_eq!(triple.vendor, Vendor::Other("".into()));
+    assert_eq!(triple.to_string(), "local");
+}
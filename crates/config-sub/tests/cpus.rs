@@ -0,0 +1,35 @@
+use config_sub::{Error, config_sub};
+
+/// Canonical CPUs are accepted with any company, and some rewrite their name.
+#[test]
+fn canonical_cpus() {
+    for (input, expected) in [
+        ("x86_64-foo-linux", "x86_64-foo-linux-gnu"),
+        ("m68k-foo-linux", "m68k-foo-linux-gnu"),
+        ("arm-acorn-riscos", "arm-acorn-riscos"),
+        ("mips64r6el-unknown-linux-gnuabi64", "mips64r6el-unknown-linux-gnuabi64"),
+        ("c4x-ti-coff", "c4x-ti-coff"),
+        ("c54x-ti-coff", "tic54x-ti-coff"),
+        ("tic54x-unknown-coff", "tic54x-unknown-coff"),
+        ("nsr-tandem-nsk", "nsr-tandem-nsk"),
+        ("nsv-tandem-nsk", "nsv-tandem-nsk"),
+        ("nsx-tandem-nsk", "nsx-tandem-nsk"),
+    ] {
+        assert_eq!(config_sub(input).as_deref(), Ok(expected), "{input}");
+    }
+}
+
+/// Unknown CPUs, and Tandem-only CPUs with another company, are rejected
+/// with the `cpu-vendor` pair the script reports.
+#[test]
+fn unrecognized_machines() {
+    for (input, machine) in [
+        ("foo-bar", "foo-unknown"),
+        ("foo", "foo-unknown"),
+        ("foo-pc-linux-gnu", "foo-pc"),
+        ("nsx-ibm-nsk", "nsx-ibm"),
+        ("nsv-ibm", "nsv-ibm"),
+    ] {
+        assert_eq!(config_sub(input), Err(Error::MachineNotRecognized(machine.into())), "{input}");
+    }
+}
@@ -0,0 +1,88 @@
+use config_sub::config_sub;
+
+/// A machine without an OS gets the OS, and any object format, that comes
+/// with it.
+#[test]
+fn default_os_from_machine() {
+    for (input, expected) in [
+        ("score", "score-unknown-elf"),
+        ("spu", "spu-unknown-elf"),
+        ("arm-acorn", "arm-acorn-riscix1.2"),
+        ("arm-rebel", "arm-rebel-linux-gnu"),
+        ("arm-semi", "arm-semi-aout"),
+        ("c4x", "c4x-unknown-coff"),
+        ("c8051", "c8051-unknown-elf"),
+        ("clipper-intergraph", "clipper-intergraph-clix"),
+        ("hexagon", "hexagon-unknown-elf"),
+        ("tic54x", "tic54x-unknown-coff"),
+        ("tic55x", "tic55x-unknown-coff"),
+        ("tic6x", "tic6x-unknown-coff"),
+        ("pdp10-dec", "pdp10-dec-tops20"),
+        ("pdp11-dec", "pdp11-dec-none"),
+        ("vax-dec", "vax-dec-ultrix4.2"),
+        ("alpha-dec", "alpha-dec-ultrix4.2"),
+        ("m68k-apollo", "m68k-apollo-domain"),
+        ("i386-sun", "i386-sun-sunos4.0.2"),
+        ("m68000-sun", "m68000-sun-sunos3"),
+        ("m68k-cisco", "m68k-cisco-aout"),
+        ("mep", "mep-unknown-elf"),
+        ("mips-sgi", "mips-sgi-irix"),
+        ("m68k-siemens", "m68k-siemens-sysv4"),
+        ("mips-cisco", "mips-cisco-elf"),
+        ("mips", "mips-unknown-elf"),
+        ("nanomips", "nanomips-unknown-elf"),
+        ("or32", "or32-unknown-coff"),
+        ("m68k-tti", "m68k-tti-sysv3"),
+        ("sparc", "sparc-sun-sunos4.1.1"),
+        ("m68k-sun", "m68k-sun-sunos4.1.1"),
+        ("pru", "pru-unknown-elf"),
+        ("powerpc-be", "powerpc-be-beos"),
+        ("powerpc-ibm", "powerpc-ibm-aix"),
+        ("i370", "i370-ibm-mvs"),
+        ("mmix-knuth", "mmix-knuth-mmixware"),
+        ("m68k-wec", "m68k-wec-proelf"),
+        ("hppa1.1-winbond", "hppa1.1-winbond-proelf"),
+        ("hppa1.1-oki", "hppa1.1-oki-proelf"),
+        ("hppa-hp", "hppa-hp-hpux"),
+        ("sh-hitachi", "sh-hitachi-hiuxwe2"),
+        ("i860", "i860-unknown-sysv"),
+        ("i860-att", "i860-att-sysv"),
+        ("m68k-ncr", "m68k-ncr-sysv"),
+        ("m68k-cbm", "m68k-cbm-amigaos"),
+        ("m88k-dg", "m88k-dg-dgux"),
+        ("m68k-dolphin", "m68k-dolphin-sysv3"),
+        ("m68k-ccur", "m68k-ccur-rtu"),
+        ("m88k-omron", "m88k-omron-luna"),
+        ("m68k-next", "m68k-next-nextstep"),
+        ("i386-sequent", "i386-sequent-ptx"),
+        ("m68k-crds", "m68k-crds-unos"),
+        ("ns32k-ns", "ns32k-ns-genix"),
+        ("pn-gould", "pn-gould-sysv"),
+        ("clipper-highlevel", "clipper-highlevel-bsd"),
+        ("ns32k-encore", "ns32k-encore-bsd"),
+        ("m68k-masscomp", "m68k-masscomp-rtu"),
+        ("m68k-rom68k", "m68k-rom68k-coff"),
+        ("m68k-rombug", "m68k-rombug-coff"),
+        ("m68k-apple", "m68k-apple-macos"),
+        ("m68k-atari", "m68k-atari-mint"),
+        ("m68k-wrs", "m68k-wrs-vxworks"),
+        ("x86_64", "x86_64-pc-none"),
+    ] {
+        assert_eq!(config_sub(input).as_deref(), Ok(expected), "{input}");
+    }
+}
+
+/// The rules are order-sensitive: `-dec` wins over `mips*-`, and `mips*-cisco`
+/// over `mips*-*`.
+#[test]
+fn default_os_order() {
+    for (input, expected) in [
+        ("mips-dec", "mips-dec-ultrix4.2"),
+        ("mips64-dec", "mips64-dec-ultrix4.2"),
+        ("mips-cisco", "mips-cisco-elf"),
+        ("mips-sgi", "mips-sgi-irix"),
+        ("mipsel", "mipsel-unknown-elf"),
+    ] {
+        assert_eq!(config_sub(input).as_deref(), Ok(expected), "{input}");
+    }
+}
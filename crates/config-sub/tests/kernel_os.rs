@@ -0,0 +1,68 @@
+use config_sub::{Error, config_sub};
+
+/// Kernel and OS combinations the script accepts.
+#[test]
+fn accepted_kernel_os_pairs() {
+    for (input, expected) in [
+        ("x86_64-pc-linux-gnu", "x86_64-pc-linux-gnu"),
+        ("x86_64-pc-linux-android", "x86_64-pc-linux-android"),
+        ("x86_64-pc-linux-llvm", "x86_64-pc-linux-llvm"),
+        ("x86_64-pc-linux-mlibc", "x86_64-pc-linux-mlibc"),
+        ("x86_64-pc-linux-musl", "x86_64-pc-linux-musl"),
+        ("x86_64-pc-linux-newlib", "x86_64-pc-linux-newlib"),
+        ("x86_64-pc-linux-relibc", "x86_64-pc-linux-relibc"),
+        ("x86_64-pc-linux-uclibc", "x86_64-pc-linux-uclibc"),
+        ("aarch64-unknown-linux-ohos", "aarch64-unknown-linux-ohos"),
+        ("arm-unknown-linux-gnueabihf", "arm-unknown-linux-gnueabihf"),
+        ("m68k-unknown-uclinux-uclibc", "m68k-unknown-uclinux-uclibc"),
+        ("m68k-unknown-uclinux-gnu", "m68k-unknown-uclinux-gnu"),
+        ("x86_64-pc-ironclad-mlibc", "x86_64-pc-ironclad-mlibc"),
+        ("x86_64-pc-managarm-mlibc", "x86_64-pc-managarm-mlibc"),
+        ("x86_64-pc-managarm-kernel", "x86_64-pc-managarm-kernel"),
+        ("x86_64-pc-windows-msvc", "x86_64-pc-windows-msvc"),
+        ("x86_64-pc-kfreebsd-gnu", "x86_64-pc-kfreebsd-gnu"),
+        ("i386-pc-knetbsd-gnu", "i386-pc-knetbsd-gnu"),
+        ("arm-unknown-netbsd-gnueabi", "arm-unknown-netbsd-gnueabi"),
+        ("i386-pc-kopensolaris-gnu", "i386-pc-kopensolaris-gnu"),
+        ("arm-unknown-vxworks-simlinux", "arm-unknown-vxworks-simlinux"),
+        ("arm-unknown-vxworks-simwindows", "arm-unknown-vxworks-simwindows"),
+        ("powerpc-unknown-vxworks-spe", "powerpc-unknown-vxworks-spe"),
+        ("x86_64-pc-nto-qnx7.1", "x86_64-pc-nto-qnx"),
+        ("i386-pc-os2-emx", "i386-pc-os2-emx"),
+        ("x86_64-pc-rtmk-nova", "x86_64-pc-rtmk-nova"),
+        ("arm-unknown-none-eabi", "arm-unknown-none-eabi"),
+        ("x86_64-pc-kfreebsd-eabi", "x86_64-pc-kfreebsd-eabi"),
+        ("x86_64-apple-ios-simulator", "x86_64-apple-ios-simulator"),
+        ("x86_64-pc-none-elf", "x86_64-pc-none-elf"),
+        ("arm-unknown-none-coff", "arm-unknown-none-coff"),
+        ("javascript-unknown-ghcjs", "javascript-unknown-ghcjs"),
+    ] {
+        assert_eq!(config_sub(input).as_deref(), Ok(expected), "{input}");
+    }
+}
+
+/// Combinations the script rejects, with the reason it gives.
+#[test]
+fn rejected_kernel_os_pairs() {
+    let not_known = |kernel: &str, os: &str| Error::KernelNotKnownToWorkWithOs(kernel.into(), os.into());
+    for (input, error) in [
+        ("x86_64-pc-hurd-gnu", not_known("hurd", "gnu")),
+        ("x86_64-pc-kfreebsd-musl", not_known("kfreebsd", "musl")),
+        ("x86_64-pc-managarm-gnu", not_known("managarm", "gnu")),
+        ("x86_64-pc-windows-gnu", not_known("windows", "gnu")),
+        ("i386-pc-nto-gnu", not_known("nto", "gnu")),
+        ("x86_64-pc-ironclad-gnu", not_known("ironclad", "gnu")),
+        ("i386-pc-os2-gnu", not_known("os2", "gnu")),
+        ("arm-unknown-foo-elf", not_known("foo", "")),
+        ("x86_64-pc-musl", Error::LibcNeedsExplicitKernel("musl".into())),
+        ("x86_64-pc-mlibc", Error::LibcNeedsExplicitKernel("mlibc".into())),
+        ("x86_64-pc-kernel", Error::OsNeedsExplicitKernel("kernel".into())),
+        ("x86_64-pc-linux-kernel", Error::KernelDoesNotSupportOs("linux".into(), "kernel".into())),
+        ("x86_64-pc-msvc", Error::OsNeedsWindows("msvc".into())),
+        ("x86_64-pc-ghcjs", Error::CpuIsNotValidWithOs("x86_64".into(), "ghcjs".into())),
+        ("javascript-unknown-linux", Error::CpuIsNotValidWithOs("javascript".into(), "gnu".into())),
+        ("x86_64-pc-linux-foo", Error::OsNotRecognized("foo".into())),
+    ] {
+        assert_eq!(config_sub(input), Err(error), "{input}");
+    }
+}
@@ -0,0 +1,22 @@
+use config_sub::config_sub;
+
+/// Kernels given without an OS pick up their default one.
+#[test]
+fn bare_kernels() {
+    for (input, expected) in [
+        ("x86_64-linux", "x86_64-pc-linux-gnu"),
+        ("x86_64-unknown-linux", "x86_64-unknown-linux-gnu"),
+        ("aarch64-linux-android", "aarch64-unknown-linux-android"),
+        ("arm-linux-gnueabihf", "arm-unknown-linux-gnueabihf"),
+        ("riscv64-linux-musl", "riscv64-unknown-linux-musl"),
+        ("x86_64-managarm", "x86_64-pc-managarm-mlibc"),
+        ("x86_64-pc-managarm", "x86_64-pc-managarm-mlibc"),
+        ("x86_64-ironclad", "x86_64-pc-ironclad-mlibc"),
+        ("x86_64-kfreebsd-gnu", "x86_64-pc-kfreebsd-gnu"),
+        ("i686-nto-qnx7.1", "i686-pc-nto-qnx"),
+        ("x86_64-uclinux", "x86_64-pc-uclinux"),
+        ("sparc-cloudabi-eabi", "sparc-unknown-cloudabi-eabi"),
+    ] {
+        assert_eq!(config_sub(input).as_deref(), Ok(expected), "{input}");
+    }
+}
@@ -0,0 +1,79 @@
+use config_sub::config_sub;
+
+/// Ad-hoc machine names resolve to the CPU and company the script picks,
+/// along with any OS they imply.
+#[test]
+fn ad_hoc_machines() {
+    for (input, expected) in [
+        ("w89k", "hppa1.1-winbond-proelf"),
+        ("op50n", "hppa1.1-oki-proelf"),
+        ("ibm360", "i370-ibm-aix"),
+        ("orion105", "clipper-highlevel-bsd"),
+        ("mac", "m68k-apple-macos"),
+        ("pmac", "powerpc-apple-macos"),
+        ("3b1", "m68000-att-sysv"),
+        ("3b2", "we32k-att-sysv"),
+        ("bluegene", "powerpc-ibm-cnk"),
+        ("dec10", "pdp10-dec-tops10"),
+        ("dec20", "pdp10-dec-tops20"),
+        ("delta", "m68k-motorola-sysv"),
+        ("dpx2100", "i386-bull-sysv3"),
+        ("dpx20", "rs6000-bull-bosx"),
+        ("encore", "ns32k-encore-bsd"),
+        ("elxsi", "elxsi-elxsi-bsd"),
+        ("fx2800", "i860-alliant-sysv"),
+        ("genix", "ns32k-ns-genix"),
+        ("hiux", "hppa1.1-hitachi-hiuxwe2"),
+        ("i386v32", "i386-pc-sysv32"),
+        ("i486v4", "i486-pc-sysv4"),
+        ("i586v", "i586-pc-sysv"),
+        ("i686sol2", "i686-pc-solaris2"),
+        ("j90", "j90-cray-unicos"),
+        ("iris", "mips-sgi-irix4"),
+        ("iris4d", "mips-sgi-irix4"),
+        ("miniframe", "m68000-convergent-sysv"),
+        ("news-3600", "mips-sony-newsos"),
+        ("next", "m68k-next-nextstep"),
+        ("m68k-next", "m68k-next-nextstep"),
+        ("np1", "np1-gould-sysv"),
+        ("op50n-hitachi", "hppa1.1-oki-proelf"),
+        ("pa-hitachi", "hppa1.1-hitachi-hiuxwe2"),
+        ("pbd", "sparc-tti-sysv3"),
+        ("pbb", "m68k-tti-sysv3"),
+        ("pc532", "ns32k-pc532-none"),
+        ("pn", "pn-gould-sysv"),
+        ("power", "power-ibm-aix"),
+        ("ps2", "i386-ibm-aix"),
+        ("rtpc", "romp-ibm-aix"),
+        ("sde", "mipsisa32-sde-elf"),
+        ("simso-wrs", "sparclite-wrs-vxworks"),
+        ("tower", "m68k-ncr-sysv"),
+        ("vx", "f301-fujitsu-uxpv"),
+        ("w65", "w65-wdc-none"),
+        ("w89k-winbond", "hppa1.1-winbond-proelf"),
+        ("sun4", "sparc-sun-sunos4.1.1"),
+        ("sun3", "m68k-sun-sunos4.1.1"),
+        ("hp300", "m68k-hp-hpux"),
+        ("pc98", "i386-pc-none"),
+        ("x64", "x86_64-pc-none"),
+        ("amd64", "x86_64-pc-none"),
+        ("i386", "i386-pc-none"),
+        ("x86_64", "x86_64-pc-none"),
+        ("i686", "i686-pc-none"),
+    ] {
+        assert_eq!(config_sub(input).as_deref(), Ok(expected), "{input}");
+    }
+}
+
+/// A CPU and company given together keep both.
+#[test]
+fn cpu_company_pairs() {
+    for (input, expected) in [
+        ("i386-sequent", "i386-sequent-ptx"),
+        ("sparc64-sun", "sparc64-sun-sunos4.1.1"),
+        ("mips-sgi", "mips-sgi-irix"),
+        ("m68k-apple", "m68k-apple-macos"),
+    ] {
+        assert_eq!(config_sub(input).as_deref(), Ok(expected), "{input}");
+    }
+}
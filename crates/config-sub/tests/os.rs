@@ -0,0 +1,118 @@
+use config_sub::{Error, config_sub};
+
+/// OS aliases are rewritten to the names config.sub settles on.
+#[test]
+fn os_normalization() {
+    for (input, expected) in [
+        ("i386-pc-auroraux", "i386-pc-auroraux"),
+        ("i386-pc-bluegene", "i386-pc-cnk"),
+        ("i386-pc-solaris1", "i386-pc-sunos4"),
+        ("i386-pc-solaris1.1", "i386-pc-sunos4.1"),
+        ("i386-pc-solaris", "i386-pc-solaris2"),
+        ("i386-pc-unixware7", "i386-pc-sysv4.2uw"),
+        ("i386-pc-ns", "i386-pc-nextstep"),
+        ("i386-pc-nextstep2", "i386-pc-nextstep2"),
+        ("i386-pc-openstep", "i386-pc-openstep3"),
+        ("i386-pc-ns4", "i386-pc-openstep4"),
+        ("i386-pc-es1800", "i386-pc-ose"),
+        ("i386-pc-chorusos", "i386-pc-chorusos"),
+        ("i386-pc-isc", "i386-pc-isc2.2"),
+        ("i386-pc-sco6", "i386-pc-sco5v6"),
+        ("i386-pc-sco5", "i386-pc-sco3.2v5"),
+        ("i386-pc-sco4", "i386-pc-sco3.2v4"),
+        ("i386-pc-sco3.2.5", "i386-pc-sco3.2v5"),
+        ("i386-pc-sco3.2v5", "i386-pc-sco3.2v5"),
+        ("i386-pc-scout", "i386-pc-scout"),
+        ("i386-pc-sco", "i386-pc-sco3.2v2"),
+        ("i386-pc-psos", "i386-pc-psos"),
+        ("i386-pc-qnx", "i386-pc-qnx"),
+        ("i386-pc-hiux", "i386-pc-hiuxwe2"),
+        ("i386-pc-lynx178", "i386-pc-lynxos178"),
+        ("i386-pc-lynx5", "i386-pc-lynxos5"),
+        ("i386-pc-lynxos", "i386-pc-lynxos"),
+        ("i386-pc-lynx", "i386-pc-lynxos"),
+        ("m68k-apple-mac7", "m68k-apple-macos7"),
+        ("i386-pc-opened", "i386-pc-openedition"),
+        ("i386-pc-os400", "i386-pc-os400"),
+        ("i386-pc-sunos5.8", "i386-pc-solaris2.8"),
+        ("i386-pc-sunos6", "i386-pc-solaris3"),
+        ("i386-pc-wince", "i386-pc-wince"),
+        ("i386-pc-utek", "i386-pc-bsd"),
+        ("i386-pc-dynix", "i386-pc-bsd"),
+        ("i386-pc-acis", "i386-pc-aos"),
+        ("i386-pc-atheos", "i386-pc-atheos"),
+        ("i386-pc-syllable", "i386-pc-syllable"),
+        ("i386-pc-386bsd", "i386-pc-bsd"),
+        ("i386-pc-ctix", "i386-pc-sysv"),
+        ("i386-pc-uts", "i386-pc-sysv"),
+        ("i386-pc-nova", "i386-pc-rtmk-nova"),
+        ("i386-pc-sinix5.4", "i386-pc-sysv5.4"),
+        ("i386-pc-sinix", "i386-pc-sysv4"),
+        ("i386-pc-tpf", "i386-pc-tpf"),
+        ("i386-pc-triton", "i386-pc-sysv3"),
+        ("i386-pc-oss", "i386-pc-sysv3"),
+        ("i386-pc-svr4", "i386-pc-sysv4"),
+        ("i386-pc-svr3", "i386-pc-sysv3"),
+        ("i386-pc-sysvr4", "i386-pc-sysv4"),
+        ("i386-pc-ose", "i386-pc-ose"),
+        ("i386-pc-mint", "i386-pc-mint"),
+        ("i386-pc-MiNT", "i386-pc-mint"),
+        ("i386-pc-dicos", "i386-pc-dicos"),
+        ("i386-pc-pikeos", "i386-pc-elf"),
+        ("i386-pc-elf", "i386-pc-elf"),
+        ("i386-pc-coff", "i386-pc-coff"),
+        ("i386-pc-mingw32ce", "i386-pc-mingw32ce"),
+        ("i386-pc-vxworks", "i386-pc-vxworks"),
+    ] {
+        assert_eq!(config_sub(input).as_deref(), Ok(expected), "{input}");
+    }
+}
+
+/// Base systems on the accepted list, with any version, pass through.
+#[test]
+fn accepted_os() {
+    for (input, expected) in [
+        ("x86_64-pc-aix7.3", "x86_64-pc-aix7.3"),
+        ("x86_64-pc-android21", "x86_64-pc-android21"),
+        ("x86_64-pc-darwin23.4.0", "x86_64-pc-darwin23.4.0"),
+        ("x86_64-pc-freebsd14.1", "x86_64-pc-freebsd14.1"),
+        ("x86_64-pc-haiku", "x86_64-pc-haiku"),
+        ("x86_64-pc-hpux11.31", "x86_64-pc-hpux11.31"),
+        ("x86_64-pc-mingw64", "x86_64-pc-mingw64"),
+        ("x86_64-pc-msdosdjgpp", "x86_64-pc-msdosdjgpp"),
+        ("x86_64-pc-netbsd10.0", "x86_64-pc-netbsd10.0"),
+        ("x86_64-pc-openbsd7.5", "x86_64-pc-openbsd7.5"),
+        ("x86_64-pc-solaris2.11", "x86_64-pc-solaris2.11"),
+        ("x86_64-pc-uefi", "x86_64-pc-uefi"),
+        ("x86_64-pc-none", "x86_64-pc-none"),
+        ("x86_64-pc-eabi", "x86_64-pc-eabi"),
+        ("x86_64-pc-eabihf", "x86_64-pc-eabihf"),
+        ("x86_64-pc-wasi", "x86_64-pc-wasi"),
+        ("x86_64-pc-zephyr", "x86_64-pc-zephyr"),
+        ("x86_64-pc-serenity", "x86_64-pc-serenity"),
+        ("x86_64-pc-redox", "x86_64-pc-redox"),
+        ("x86_64-pc-cygwin", "x86_64-pc-cygwin"),
+        ("x86_64-pc-msys", "x86_64-pc-msys"),
+        ("x86_64-pc-ios17.0", "x86_64-pc-ios17.0"),
+        ("x86_64-pc-rtems6", "x86_64-pc-rtems6"),
+        ("x86_64-pc-fuchsia", "x86_64-pc-fuchsia"),
+        ("x86_64-pc-elf", "x86_64-pc-elf"),
+        ("x86_64-pc-coff", "x86_64-pc-coff"),
+    ] {
+        assert_eq!(config_sub(input).as_deref(), Ok(expected), "{input}");
+    }
+}
+
+/// Anything else fails with the OS it could not recognize.
+#[test]
+fn unrecognized_os() {
+    for (input, os) in [
+        ("x86_64-pc-foo", "foo"),
+        ("arm-foo", "foo"),
+        ("i386-pc", "pc"),
+        ("x86_64-pc-none-foo", "foo"),
+        ("x86_64-pc-nuttx", "nuttx"),
+    ] {
+        assert_eq!(config_sub(input), Err(Error::OsNotRecognized(os.into())), "{input}");
+    }
+}
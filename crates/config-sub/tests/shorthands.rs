@@ -0,0 +1,64 @@
+use config_sub::config_sub;
+
+/// One-word aliases expand to the basic machine and OS the script gives.
+#[test]
+fn single_component_shorthands() {
+    for (input, expected) in [
+        ("386bsd", "i386-pc-bsd"),
+        ("a29khif", "a29k-amd-udi"),
+        ("adobe68k", "m68010-adobe-scout"),
+        ("alliant", "fx80-alliant-none"),
+        ("altos", "m68k-altos-sysv"),
+        ("am29k", "a29k-none-bsd"),
+        ("amdahl", "580-amdahl-sysv"),
+        ("amiga", "m68k-unknown-none"),
+        ("amigaos", "m68k-unknown-amigaos"),
+        ("apollo68", "m68k-apollo-sysv"),
+        ("aux", "m68k-apple-aux"),
+        ("cray", "j90-cray-unicos"),
+        ("crds", "m68k-crds-unos"),
+        ("da30", "m68k-da30-none"),
+        ("decstation", "mips-dec-ultrix4.2"),
+        ("h8300hms", "h8300-hitachi-hms"),
+        ("hp300bsd", "m68k-hp-bsd"),
+        ("i386mach", "i386-mach-mach"),
+        ("isi386", "isi386-pc-none"),
+        ("m68knommu", "m68k-unknown-linux-gnu"),
+        ("mingw32", "i686-pc-mingw32"),
+        ("mingw32ce", "arm-unknown-mingw32ce"),
+        ("morphos", "powerpc-unknown-morphos"),
+        ("msdos", "i386-pc-msdos"),
+        ("msys", "i686-pc-msys"),
+        ("mvs", "i370-ibm-mvs"),
+        ("necv70", "v70-nec-sysv"),
+        ("os400", "powerpc-ibm-os400"),
+        ("ps2", "i386-ibm-aix"),
+        ("rm400", "mips-siemens-sysv4"),
+        ("sei", "mips-sei-seiux"),
+        ("sun2os3", "m68000-sun-sunos3"),
+        ("sun3os4", "m68k-sun-sunos4"),
+        ("sun4sol2", "sparc-sun-solaris2"),
+        ("tpf", "s390x-ibm-tpf"),
+        ("udi29k", "a29k-amd-udi"),
+        ("ultra3", "a29k-nyu-sym1"),
+        ("vms", "vax-dec-vms"),
+        ("vsta", "i386-pc-vsta"),
+        ("vxworks68", "m68k-wrs-vxworks"),
+        ("xbox", "i686-pc-mingw32"),
+        ("xps", "xps100-honeywell-none"),
+    ] {
+        assert_eq!(config_sub(input).as_deref(), Ok(expected), "{input}");
+    }
+}
+
+/// The few shorthands that contain a dash are not split into machine and OS.
+#[test]
+fn shorthands_with_a_dash() {
+    for (input, expected) in [
+        ("convex-c1", "c1-convex-bsd"),
+        ("convex-c38", "c38-convex-bsd"),
+        ("decstation-3100", "mips-dec-ultrix4.2"),
+    ] {
+        assert_eq!(config_sub(input).as_deref(), Ok(expected), "{input}");
+    }
+}
@@ -0,0 +1,26 @@
+use config_sub::config_sub;
+
+/// Vendors inferred from the OS when the input omits one.
+#[test]
+fn inferred_vendors() {
+    for (input, expected) in [
+        ("x86_64-linux", "x86_64-pc-linux-gnu"),
+        ("hppa-hpux", "hppa-hp-hpux"),
+        ("rs6000-aix", "rs6000-ibm-aix"),
+        ("powerpc-aix7.3", "powerpc-ibm-aix7.3"),
+        ("sparc-solaris", "sparc-sun-solaris2"),
+        ("sparc-sunos4", "sparc-sun-sunos4"),
+        ("m68k-vxworks", "m68k-wrs-vxworks"),
+        ("i386-go32", "i386-pc-go32"),
+        ("m68k-aux", "m68k-apple-aux"),
+        ("x86_64-mingw32", "x86_64-pc-mingw32"),
+        ("x86_64-cygwin", "x86_64-pc-cygwin"),
+        ("i386-sysv4", "i386-pc-sysv4"),
+        ("s390x-tpf", "s390x-ibm-tpf"),
+        ("m68k-mint", "m68k-atari-mint"),
+        ("mips-irix", "mips-unknown-irix"),
+        ("arm-riscos", "arm-unknown-riscos"),
+    ] {
+        assert_eq!(config_sub(input).as_deref(), Ok(expected), "{input}");
+    }
+}